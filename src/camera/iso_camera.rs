@@ -2,6 +2,9 @@ use crate::camera::Camera;
 use crate::vector3;
 use crate::vector3::Vector3;
 
+/// Default depth of the near clipping plane
+const DEFAULT_NEAR: f64 = 0.0;
+
 /// Isometric camera
 pub struct IsoCamera {
     /// Point we are observing from
@@ -12,47 +15,84 @@ pub struct IsoCamera {
     orientation: Vector3,
     /// Size of screen the world should be projected onto, (width, height)
     screen_size: (u16, u16),
+    /// Width of a pixel divided by its height
+    pixel_aspect: f64,
     screen_top_left: Vector3,
+    /// Pixels per unit of distance in the world
+    scale: f64,
+    /// Depth of the near clipping plane
+    near: f64,
+    /// Depth of the far clipping plane, if any
+    far: Option<f64>,
 }
 
 impl Camera for IsoCamera {
     fn update_screen_size(&mut self, screen_size: (u16, u16)) {
         self.screen_size = screen_size;
+        self.recalculate();
     }
 
     fn get_screen_size(&self) -> (u16, u16) {
         self.screen_size
     }
 
+    fn update_pixel_aspect(&mut self, pixel_aspect: f64) {
+        self.pixel_aspect = pixel_aspect;
+        self.recalculate();
+    }
+
+    fn get_pixel_aspect(&self) -> f64 {
+        self.pixel_aspect
+    }
+
     fn update_observation_point(&mut self, point: Vector3, direction: Vector3) {
         self.observation_point = point;
-        self.observation_direction = direction;
+        self.observation_direction = direction.normalize();
+        self.recalculate();
     }
 
     fn get_observation_point(&self) -> (Vector3, Vector3) {
         (self.observation_point, self.observation_direction)
     }
 
+    fn get_clip_planes(&self) -> (f64, Option<f64>) {
+        (self.near, self.far)
+    }
+
+    fn update_clip_planes(&mut self, near: f64, far: Option<f64>) {
+        self.near = near;
+        self.far = far;
+    }
+
+    fn is_orthographic(&self) -> bool {
+        true
+    }
+
+    /// Every point is seen from the same direction
+    fn get_view_direction(&self, _vec: Vector3) -> Vector3 {
+        -self.observation_direction.normalize()
+    }
+
     fn recalculate(&mut self) {
         let up = self.orientation;
         let left = up * self.observation_direction;
 
         self.screen_top_left = self.observation_point
-            + (self.screen_size.0 as f64 / 2.0) * left
-            + (self.screen_size.1 as f64 / 2.0) * up;
+            + (self.screen_size.0 as f64 / 2.0 / self.scale) * left
+            + (self.screen_size.1 as f64 / 2.0 / self.scale / self.pixel_aspect) * up;
     }
 
     fn project_vector(&self, vec: Vector3) -> Vector3 {
-        let normal_vector = self.observation_direction;
-        let incoming = (vec - self.observation_point).dot(normal_vector);
-
-        if incoming.abs() < f64::EPSILON {
-            (vec - self.screen_top_left).neg_y()
-        } else {
-            let pt = vec + normal_vector * (incoming / (-normal_vector.dot(normal_vector)));
-
-            (pt - self.screen_top_left).neg_y() + Vector3::new(0.0, 0.0, vec.distance_to(pt))
-        }
+        let up = self.orientation;
+        let right = self.observation_direction * up;
+        let offset = vec - self.screen_top_left;
+        let depth = (vec - self.observation_point).dot(self.observation_direction);
+
+        Vector3::new(
+            offset.dot(right) * self.scale,
+            -offset.dot(up) * self.scale * self.pixel_aspect,
+            depth,
+        )
     }
 }
 
@@ -68,7 +108,11 @@ impl IsoCamera {
             observation_direction: observation_direction.normalize(),
             orientation: orientation.normalize(),
             screen_size,
+            pixel_aspect: 1.0,
             screen_top_left: Vector3::zero(),
+            scale: 1.0,
+            near: DEFAULT_NEAR,
+            far: None,
         };
 
         camera.recalculate();
@@ -82,11 +126,25 @@ impl IsoCamera {
             observation_direction: vector3!(0, 0, -1).normalize(),
             orientation: vector3!(0, 1, 0).normalize(),
             screen_size,
+            pixel_aspect: 1.0,
             screen_top_left: Vector3::zero(),
+            scale: 1.0,
+            near: DEFAULT_NEAR,
+            far: None,
         };
 
         camera.recalculate();
 
         camera
     }
+
+    /// Zoom so that one unit of distance in the world covers `scale` pixels
+    pub fn update_scale(&mut self, scale: f64) {
+        self.scale = scale;
+        self.recalculate();
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
 }
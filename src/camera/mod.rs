@@ -9,8 +9,35 @@ use crate::vector3::Vector3;
 pub trait Camera {
     fn update_screen_size(&mut self, screen_size: (u16, u16));
     fn get_screen_size(&self) -> (u16, u16);
+    /// Set the width of a pixel divided by its height. The projection is squeezed vertically to
+    /// match, so shapes keep their proportions on pixels that aren't square.
+    fn update_pixel_aspect(&mut self, pixel_aspect: f64);
+    fn get_pixel_aspect(&self) -> f64;
     fn update_observation_point(&mut self, point: Vector3, direction: Vector3);
     fn get_observation_point(&self) -> (Vector3, Vector3);
+    /// Depths of the near and far clipping planes. Anything closer than the near plane or further
+    /// than the far plane is clipped away before it is projected.
+    fn get_clip_planes(&self) -> (f64, Option<f64>);
+    fn update_clip_planes(&mut self, near: f64, far: Option<f64>);
+
+    /// Distance of `vec` in front of the observation point, along the direction of observation.
+    /// Negative if the point is behind the observer.
+    fn get_depth(&self, vec: Vector3) -> f64 {
+        let (point, direction) = self.get_observation_point();
+
+        (vec - point).dot(direction.normalize())
+    }
+
+    /// Whether the projection keeps parallel lines parallel, so values can be interpolated
+    /// linearly across the screen
+    fn is_orthographic(&self) -> bool {
+        false
+    }
+
+    /// Direction from `vec` towards the observer, used to light surfaces
+    fn get_view_direction(&self, vec: Vector3) -> Vector3 {
+        (self.get_observation_point().0 - vec).normalize()
+    }
 
     /// Make recalculations based on camera parameters. Called when camera parameters are updated.
     fn recalculate(&mut self);
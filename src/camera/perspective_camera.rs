@@ -4,6 +4,10 @@ use crate::camera::Camera;
 use crate::vector3;
 use crate::vector3::Vector3;
 
+/// Default depth of the near clipping plane. Points very close to the observer project far off
+/// the screen, so they are clipped too.
+const DEFAULT_NEAR: f64 = 0.1;
+
 /// Perspective camera
 pub struct PerspectiveCamera {
     /// Field of view, in degrees
@@ -16,7 +20,13 @@ pub struct PerspectiveCamera {
     orientation: Vector3,
     /// Size of screen the world should be projected onto, (width, height)
     screen_size: (u16, u16),
+    /// Width of a pixel divided by its height
+    pixel_aspect: f64,
     screen_top_left: Vector3,
+    /// Depth of the near clipping plane
+    near: f64,
+    /// Depth of the far clipping plane, if any
+    far: Option<f64>,
     screen_distance: f64,
 }
 
@@ -30,6 +40,15 @@ impl Camera for PerspectiveCamera {
         self.screen_size
     }
 
+    fn update_pixel_aspect(&mut self, pixel_aspect: f64) {
+        self.pixel_aspect = pixel_aspect;
+        self.recalculate();
+    }
+
+    fn get_pixel_aspect(&self) -> f64 {
+        self.pixel_aspect
+    }
+
     fn update_observation_point(&mut self, point: Vector3, direction: Vector3) {
         self.observation_point = point;
         self.observation_direction = direction;
@@ -40,6 +59,15 @@ impl Camera for PerspectiveCamera {
         (self.observation_point, self.observation_direction)
     }
 
+    fn get_clip_planes(&self) -> (f64, Option<f64>) {
+        (self.near, self.far)
+    }
+
+    fn update_clip_planes(&mut self, near: f64, far: Option<f64>) {
+        self.near = near;
+        self.far = far;
+    }
+
     fn recalculate(&mut self) {
         self.screen_distance =
             self.screen_size.0 as f64 / (2.0 * ((self.fov as f64).to_radians() / 2.0).tan());
@@ -50,7 +78,7 @@ impl Camera for PerspectiveCamera {
 
         self.screen_top_left = self.observation_point
             + (self.screen_size.0 as f64 / 2.0) * left
-            + (self.screen_size.1 as f64 / 2.0) * up
+            + (self.screen_size.1 as f64 / 2.0 / self.pixel_aspect) * up
             + self.screen_distance * forward;
     }
 
@@ -67,6 +95,9 @@ impl Camera for PerspectiveCamera {
         let pt = vec + project_direction * distance_from_screen;
         // Convert to top-left corner of screen being (0, 0)
         let position_on_screen = (pt - self.screen_top_left).neg_y().with_z(0.0);
+        // Each pixel covers 1 unit of the screen horizontally, and 1 / pixel_aspect vertically
+        let position_on_screen =
+            position_on_screen.with_y(position_on_screen.y * self.pixel_aspect);
 
         if dot.abs() < f64::EPSILON {
             position_on_screen.with_z(0.0)
@@ -99,7 +130,10 @@ impl PerspectiveCamera {
             observation_direction: observation_direction.normalize(),
             orientation: orientation.normalize(),
             screen_size,
+            pixel_aspect: 1.0,
             screen_top_left: Vector3::zero(),
+            near: DEFAULT_NEAR,
+            far: None,
             screen_distance: 0.0,
         };
 
@@ -115,7 +149,10 @@ impl PerspectiveCamera {
             observation_direction: vector3!(0, 0, -1).normalize(),
             orientation: vector3!(0, 1, 0).normalize(),
             screen_size,
+            pixel_aspect: 1.0,
             screen_top_left: Vector3::zero(),
+            near: DEFAULT_NEAR,
+            far: None,
             screen_distance: 0.0,
         };
 
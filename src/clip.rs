@@ -0,0 +1,151 @@
+use crate::vector3::Vector3;
+
+/// Values that can be linearly interpolated, so primitives made of them can be split where they
+/// cross a clipping plane
+pub trait Interpolate: Copy {
+    /// Value `t` of the way from `self` to `other`
+    fn interpolate(self, other: Self, t: f64) -> Self;
+}
+
+impl Interpolate for Vector3 {
+    fn interpolate(self, other: Vector3, t: f64) -> Vector3 {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(self, other: f64, t: f64) -> f64 {
+        self + (other - self) * t
+    }
+}
+
+/// Vertices can carry attributes, which are interpolated along with their position
+impl<A: Interpolate, B: Interpolate> Interpolate for (A, B) {
+    fn interpolate(self, other: (A, B), t: f64) -> (A, B) {
+        (
+            self.0.interpolate(other.0, t),
+            self.1.interpolate(other.1, t),
+        )
+    }
+}
+
+/// Clip a line to the part where `distance` is non-negative. Returns None if nothing is left.
+pub fn clip_line<T: Interpolate>(start: T, end: T, distance: impl Fn(T) -> f64) -> Option<(T, T)> {
+    let (start_distance, end_distance) = (distance(start), distance(end));
+
+    if start_distance < 0.0 && end_distance < 0.0 {
+        None
+    } else if start_distance >= 0.0 && end_distance >= 0.0 {
+        Some((start, end))
+    } else {
+        let t = start_distance / (start_distance - end_distance);
+        let intersection = start.interpolate(end, t);
+
+        if start_distance < 0.0 {
+            Some((intersection, end))
+        } else {
+            Some((start, intersection))
+        }
+    }
+}
+
+/// Clip a convex polygon to the part where `distance` is non-negative, using the
+/// Sutherland-Hodgman algorithm. The winding order is preserved.
+pub fn clip_polygon<T: Interpolate>(polygon: &[T], distance: impl Fn(T) -> f64) -> Vec<T> {
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (current_distance, next_distance) = (distance(current), distance(next));
+
+        if current_distance >= 0.0 {
+            result.push(current);
+        }
+        if (current_distance >= 0.0) != (next_distance >= 0.0) {
+            let t = current_distance / (current_distance - next_distance);
+
+            result.push(current.interpolate(next, t));
+        }
+    }
+
+    result
+}
+
+/// Clip a line to the depth range [`near`, `far`], where `depth` gives the distance of a point in
+/// front of the camera
+pub fn clip_line_to_depth<T: Interpolate>(
+    (start, end): (T, T),
+    depth: impl Fn(T) -> f64,
+    near: f64,
+    far: Option<f64>,
+) -> Option<(T, T)> {
+    let (start, end) = clip_line(start, end, |point| depth(point) - near)?;
+
+    match far {
+        Some(far) => clip_line(start, end, |point| far - depth(point)),
+        None => Some((start, end)),
+    }
+}
+
+/// Clip a triangle to the depth range [`near`, `far`], where `depth` gives the distance of a point
+/// in front of the camera. The part that is left is split into triangles with the same winding
+/// order as the original.
+pub fn clip_triangle_to_depth<T: Interpolate>(
+    (a, b, c): (T, T, T),
+    depth: impl Fn(T) -> f64,
+    near: f64,
+    far: Option<f64>,
+) -> Vec<(T, T, T)> {
+    let mut polygon = clip_polygon(&[a, b, c], |point| depth(point) - near);
+
+    if let Some(far) = far {
+        polygon = clip_polygon(&polygon, |point| far - depth(point));
+    }
+
+    // Triangle fan around the first vertex
+    (2..polygon.len())
+        .map(|i| (polygon[0], polygon[i - 1], polygon[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector3;
+
+    fn depth(point: Vector3) -> f64 {
+        point.z
+    }
+
+    #[test]
+    fn clip_lines() {
+        let line = (vector3!(0, 0, -10), vector3!(0, 10, 10));
+
+        assert_eq!(
+            clip_line_to_depth(line, depth, 1.0, None),
+            Some((vector3!(0, 5.5, 1), vector3!(0, 10, 10)))
+        );
+        assert_eq!(
+            clip_line_to_depth(line, depth, 1.0, Some(5.0)),
+            Some((vector3!(0, 5.5, 1), vector3!(0, 7.5, 5)))
+        );
+        assert_eq!(clip_line_to_depth(line, depth, 11.0, None), None);
+    }
+
+    #[test]
+    fn clip_triangles() {
+        let triangle = (vector3!(0, 0, 2), vector3!(4, 0, -2), vector3!(0, 4, -2));
+
+        // One vertex in front, so the result is a smaller triangle
+        assert_eq!(
+            clip_triangle_to_depth(triangle, depth, 0.0, None),
+            vec![(vector3!(0, 0, 2), vector3!(2, 0, 0), vector3!(0, 2, 0))]
+        );
+        // Two vertices in front, so the result is a quad split into two triangles
+        assert_eq!(
+            clip_triangle_to_depth(triangle, |point| -point.z, 0.0, None).len(),
+            2
+        );
+        assert!(clip_triangle_to_depth(triangle, depth, 3.0, None).is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use crate::vector3::Vector3;
+
+/// Which edges are derived from the triangles of a mesh, so meshes without edges of their own get
+/// an outline, without the edges between faces that lie flat against each other
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FeatureEdges {
+    /// Edges between a face turned towards the observer and a face turned away from it
+    pub silhouettes: bool,
+    /// Edges that aren't shared by exactly two faces, such as the border of an open mesh
+    pub boundaries: bool,
+    /// Edges where the faces on either side meet at more than this angle, in radians
+    pub crease_angle: Option<f64>,
+}
+
+impl Default for FeatureEdges {
+    /// Silhouettes, boundaries, and creases sharper than 30 degrees
+    fn default() -> FeatureEdges {
+        FeatureEdges {
+            silhouettes: true,
+            boundaries: true,
+            crease_angle: Some(30f64.to_radians()),
+        }
+    }
+}
+
+impl FeatureEdges {
+    /// Feature edges of the mesh made of `triangles`, as tuples of indexes corresponding to
+    /// `vertices`. `view` gives the direction from a point towards the observer, which decides
+    /// where the silhouette is.
+    pub fn extract(
+        &self,
+        vertices: &[Vector3],
+        triangles: &[(usize, usize, usize)],
+        view: impl Fn(Vector3) -> Vector3,
+    ) -> Vec<(usize, usize)> {
+        // Faces around each edge, with the lower vertex index first
+        let mut edge_faces: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+
+        for (index, &(a, b, c)) in triangles.iter().enumerate() {
+            for (start, end) in [(a, b), (b, c), (c, a)] {
+                edge_faces
+                    .entry((start.min(end), start.max(end)))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        let normals: Vec<Vector3> = triangles
+            .iter()
+            .map(|&(a, b, c)| (vertices[b] - vertices[a]) * (vertices[c] - vertices[a]))
+            .collect();
+        let is_front_facing = |index: usize| {
+            let (a, b, c) = triangles[index];
+            let centroid = (vertices[a] + vertices[b] + vertices[c]) / 3.0;
+
+            normals[index].dot(view(centroid)) > 0.0
+        };
+
+        edge_faces
+            .into_iter()
+            .filter(|(_, faces)| match faces[..] {
+                [first, second] => {
+                    let is_silhouette =
+                        self.silhouettes && is_front_facing(first) != is_front_facing(second);
+                    let is_crease = self
+                        .crease_angle
+                        .is_some_and(|angle| normals[first].angle(normals[second]) > angle);
+
+                    is_silhouette || is_crease
+                }
+                _ => self.boundaries,
+            })
+            .map(|(edge, _)| edge)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector3;
+
+    #[test]
+    fn extract_edges() {
+        // Two squares folded along the edge from vertex 1 to vertex 4, each split along a
+        // diagonal
+        let vertices = [
+            vector3!(0, 0, 0),
+            vector3!(1, 0, 0),
+            vector3!(2, 0, 1),
+            vector3!(0, 1, 0),
+            vector3!(1, 1, 0),
+            vector3!(2, 1, 1),
+        ];
+        let triangles = [(0, 1, 4), (0, 4, 3), (1, 2, 5), (1, 5, 4)];
+        let boundary = vec![(0, 1), (0, 3), (1, 2), (2, 5), (3, 4), (4, 5)];
+        let from_above = |_| vector3!(0, 0, 1);
+        let none = FeatureEdges {
+            silhouettes: false,
+            boundaries: false,
+            crease_angle: None,
+        };
+
+        // The diagonals are never features
+        assert_eq!(
+            FeatureEdges {
+                boundaries: true,
+                ..none
+            }
+            .extract(&vertices, &triangles, from_above),
+            boundary
+        );
+        // The fold is a crease at 45 degrees
+        let creases = |angle: f64| FeatureEdges {
+            crease_angle: Some(angle.to_radians()),
+            ..none
+        };
+        assert_eq!(
+            creases(30.0).extract(&vertices, &triangles, from_above),
+            vec![(1, 4)]
+        );
+        assert!(
+            creases(60.0)
+                .extract(&vertices, &triangles, from_above)
+                .is_empty()
+        );
+        // Seen from below on the side, one half is turned away, so the fold is on the silhouette
+        let silhouettes = FeatureEdges {
+            silhouettes: true,
+            ..none
+        };
+        assert!(
+            silhouettes
+                .extract(&vertices, &triangles, from_above)
+                .is_empty()
+        );
+        assert_eq!(
+            silhouettes.extract(&vertices, &triangles, |_| vector3!(-1, 0, -0.5)),
+            vec![(1, 4)]
+        );
+    }
+}
@@ -0,0 +1,944 @@
+use crate::{
+    camera::Camera,
+    clip::{Interpolate, clip_line_to_depth, clip_triangle_to_depth},
+    lighting::{Illumination, Light, ShadingMode, face_normal},
+    render::{
+        bresenham_line_3d, depth_slope, edge_function_triangle_3d, perspective_correct, wu_line_3d,
+    },
+    render_mode::RenderMode,
+    subcell::SubCellMode,
+    terminal::{Cell, Color, Decor, Style},
+    texture::Uv,
+    vector3,
+    vector3::Vector3,
+    world_object::WorldObject,
+};
+
+/// Vertex of a face, with the values that are interpolated across it
+#[derive(Clone, Copy)]
+struct FaceVertex {
+    position: Vector3,
+    /// Barycentric weights of the vertex in the face before clipping, which the light falling on
+    /// the corners of the face is blended with
+    corners: (f64, f64, f64),
+    uv: Uv,
+}
+
+impl FaceVertex {
+    /// Point inside the triangle `vertices` with the barycentric `weights`
+    fn blend(
+        vertices: (FaceVertex, FaceVertex, FaceVertex),
+        weights: (f64, f64, f64),
+    ) -> FaceVertex {
+        let (a, b, c) = vertices;
+        let blend = |a: f64, b: f64, c: f64| a * weights.0 + b * weights.1 + c * weights.2;
+
+        FaceVertex {
+            position: a.position * weights.0 + b.position * weights.1 + c.position * weights.2,
+            corners: (
+                blend(a.corners.0, b.corners.0, c.corners.0),
+                blend(a.corners.1, b.corners.1, c.corners.1),
+                blend(a.corners.2, b.corners.2, c.corners.2),
+            ),
+            uv: (blend(a.uv.0, b.uv.0, c.uv.0), blend(a.uv.1, b.uv.1, c.uv.1)),
+        }
+    }
+}
+
+impl Interpolate for FaceVertex {
+    fn interpolate(self, other: FaceVertex, t: f64) -> FaceVertex {
+        FaceVertex::blend((self, other, other), (1.0 - t, t, 0.0))
+    }
+}
+
+/// Lower number -> higher priority
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum DrawType {
+    Vertex,
+    Edge,
+    Face,
+    /// Face that hides what is behind it without being drawn
+    Occluder,
+    None,
+}
+
+/// Offsets added to the depth of each type of primitive before depth testing, so primitives at
+/// the same depth don't fight over pixels. A larger bias pushes a primitive further back.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DepthBias {
+    pub vertex: f64,
+    pub edge: f64,
+    pub face: f64,
+    /// Extra face bias per unit of depth change between neighbouring pixels, like the factor of a
+    /// polygon offset. Faces seen at a steep angle need more bias, since their depth at a pixel
+    /// can differ a lot from the depth of a line drawn along their border.
+    pub face_slope: f64,
+}
+
+/// How edges hidden behind faces are drawn. Hidden edges are drawn in dashes `dash` pixels long,
+/// separated by gaps `gap` pixels long.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Stipple {
+    pub dash: u16,
+    pub gap: u16,
+    /// Color of hidden edges, instead of the color of the edge itself
+    pub color: Option<Color>,
+}
+
+impl Stipple {
+    /// Hidden edges are not drawn at all
+    pub fn none() -> Stipple {
+        Stipple {
+            dash: 0,
+            gap: 0,
+            color: None,
+        }
+    }
+
+    pub fn dashed(dash: u16, gap: u16) -> Stipple {
+        Stipple {
+            dash,
+            gap,
+            color: None,
+        }
+    }
+
+    pub fn with_color(self, color: Color) -> Stipple {
+        Stipple {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    /// Style of the pixel `step` pixels along an edge drawn with `style`, where the edge is
+    /// hidden. None if the pixel falls in a gap.
+    fn hide(&self, style: Style, step: u64) -> Option<Style> {
+        if self.dash == 0 || step % (self.dash as u64 + self.gap as u64) >= self.dash as u64 {
+            return None;
+        }
+
+        Some((style.0, self.color.unwrap_or(style.1), style.2))
+    }
+}
+
+impl Default for Stipple {
+    fn default() -> Stipple {
+        Stipple::none()
+    }
+}
+
+impl Default for DepthBias {
+    /// Faces are pushed slightly behind the edges and vertices on their border
+    fn default() -> DepthBias {
+        DepthBias {
+            vertex: -0.01,
+            edge: 0.0,
+            face: 0.01,
+            face_slope: 0.75,
+        }
+    }
+}
+
+#[allow(unused)]
+#[derive(Clone, Copy)]
+struct Pixel {
+    pub frame: u64,
+    pub style: Style,
+    pub dist: f64,
+    pub shape_id: u64,
+    pub draw_type: DrawType,
+    /// Style of an edge hidden behind the pixel, shown through faces
+    pub hidden_edge: Option<Style>,
+}
+
+impl Pixel {
+    /// Style the pixel is shown with, or None if nothing is shown
+    fn get_style(&self) -> Option<Style> {
+        match self.draw_type {
+            DrawType::Vertex | DrawType::Edge => Some(self.style),
+            DrawType::Face => Some(self.hidden_edge.unwrap_or(self.style)),
+            DrawType::Occluder => self.hidden_edge,
+            DrawType::None => None,
+        }
+    }
+}
+
+const EMPTY_PIXEL: Pixel = Pixel {
+    frame: 0,
+    style: (' ', Color::Reset, Decor::None),
+    dist: 0.0,
+    draw_type: DrawType::None,
+    shape_id: 0,
+    hidden_edge: None,
+};
+
+/// Pixels of a frame, which world objects are rasterized into. The framebuffer is measured in
+/// cells, and each cell covers `subcell_mode.scale()` pixels.
+pub struct Framebuffer {
+    width: u16,
+    height: u16,
+    subcell_mode: SubCellMode,
+    depth_bias: DepthBias,
+    /// Which parts of objects are drawn, unless an object picks its own
+    render_mode: RenderMode,
+    hidden_edges: Stipple,
+    /// Pixels of the current frame, row by row
+    pixels: Vec<Pixel>,
+}
+
+impl Framebuffer {
+    /// Create a framebuffer `size` cells large, (width, height). The size is limited so the screen
+    /// is at most `u16::MAX` pixels in each direction.
+    pub fn new(size: (u16, u16), subcell_mode: SubCellMode) -> Framebuffer {
+        let size = Framebuffer::limit_size(size, subcell_mode);
+        let mut framebuffer = Framebuffer {
+            width: size.0,
+            height: size.1,
+            subcell_mode,
+            depth_bias: DepthBias::default(),
+            render_mode: RenderMode::default(),
+            hidden_edges: Stipple::default(),
+            pixels: vec![],
+        };
+
+        framebuffer.allocate();
+
+        framebuffer
+    }
+
+    /// Size in cells, (width, height)
+    pub fn get_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Resize to `size` cells, (width, height), limited like in `new`. This clears the
+    /// framebuffer.
+    pub fn resize(&mut self, size: (u16, u16)) {
+        (self.width, self.height) = Framebuffer::limit_size(size, self.subcell_mode);
+        self.allocate();
+    }
+
+    /// Largest size up to `size` cells whose pixels can be counted in a `u16`
+    fn limit_size(size: (u16, u16), subcell_mode: SubCellMode) -> (u16, u16) {
+        let scale = subcell_mode.scale();
+
+        (
+            size.0.min(u16::MAX / scale.0),
+            size.1.min(u16::MAX / scale.1),
+        )
+    }
+
+    pub fn get_subcell_mode(&self) -> SubCellMode {
+        self.subcell_mode
+    }
+
+    /// Change how pixels are packed into cells. This changes the size reported by
+    /// `get_screen_size`, so cameras need to be given the new size. This clears the framebuffer.
+    pub fn set_subcell_mode(&mut self, subcell_mode: SubCellMode) {
+        self.subcell_mode = subcell_mode;
+        (self.width, self.height) = Framebuffer::limit_size(self.get_size(), subcell_mode);
+        self.allocate();
+    }
+
+    pub fn get_depth_bias(&self) -> DepthBias {
+        self.depth_bias
+    }
+
+    pub fn set_depth_bias(&mut self, depth_bias: DepthBias) {
+        self.depth_bias = depth_bias;
+    }
+
+    pub fn get_render_mode(&self) -> RenderMode {
+        self.render_mode
+    }
+
+    pub fn set_render_mode(&mut self, render_mode: RenderMode) {
+        self.render_mode = render_mode;
+    }
+
+    pub fn get_hidden_edges(&self) -> Stipple {
+        self.hidden_edges
+    }
+
+    /// Draw edges hidden behind faces with `hidden_edges`, instead of leaving them out
+    pub fn set_hidden_edges(&mut self, hidden_edges: Stipple) {
+        self.hidden_edges = hidden_edges;
+    }
+
+    /// Size of the screen in pixels, (width, height)
+    pub fn get_screen_size(&self) -> (u16, u16) {
+        let scale = self.subcell_mode.scale();
+
+        (self.width * scale.0, self.height * scale.1)
+    }
+
+    /// Reset every pixel to empty
+    pub fn clear(&mut self) {
+        self.pixels.fill(EMPTY_PIXEL);
+    }
+
+    /// Reallocate the pixels to match the current size and sub-cell mode
+    fn allocate(&mut self) {
+        let (width, height) = self.get_screen_size();
+
+        self.pixels = vec![EMPTY_PIXEL; width as usize * height as usize];
+    }
+
+    // Plot character, assuming x and y are in bounds
+    #[allow(clippy::too_many_arguments)]
+    fn plot_character(
+        &mut self,
+        x: u16,
+        y: u16,
+        depth: f64,
+        style: Style,
+        shape_id: u64,
+        draw_type: DrawType,
+        frame: u64,
+    ) {
+        let depth = depth
+            + match draw_type {
+                DrawType::Vertex => self.depth_bias.vertex,
+                DrawType::Edge => self.depth_bias.edge,
+                DrawType::Face | DrawType::Occluder => self.depth_bias.face,
+                DrawType::None => 0.0,
+            };
+        let index = y as usize * self.get_screen_size().0 as usize + x as usize;
+        let cur_pixel = &self.pixels[index];
+
+        let is_same_frame = cur_pixel.frame == frame;
+        let is_drawtype_none = cur_pixel.draw_type == DrawType::None;
+        let is_in_front = cur_pixel.dist > depth;
+
+        if !is_same_frame || is_drawtype_none || is_in_front {
+            // Edges hidden behind the pixel stay hidden behind whatever replaces it
+            let hidden_edge = if is_same_frame {
+                cur_pixel.hidden_edge
+            } else {
+                None
+            };
+
+            self.pixels[index] = Pixel {
+                frame,
+                style,
+                dist: depth,
+                shape_id,
+                draw_type,
+                hidden_edge,
+            }
+        }
+    }
+
+    /// Plot a pixel of an edge, assuming x and y are in bounds. Where faces hide the pixel, now
+    /// or once they are plotted, it is shown with `hidden_style` instead, if any.
+    #[allow(clippy::too_many_arguments)]
+    fn plot_edge(
+        &mut self,
+        x: u16,
+        y: u16,
+        depth: f64,
+        style: Style,
+        hidden_style: Option<Style>,
+        shape_id: u64,
+        frame: u64,
+    ) {
+        self.plot_character(x, y, depth, style, shape_id, DrawType::Edge, frame);
+
+        let index = y as usize * self.get_screen_size().0 as usize + x as usize;
+        let pixel = &mut self.pixels[index];
+
+        if pixel.hidden_edge.is_none() {
+            pixel.hidden_edge = hidden_style;
+        }
+    }
+
+    fn is_in_bounds(&self, x: i64, y: i64) -> bool {
+        let (width, height) = self.get_screen_size();
+
+        x >= 0 && x < width as i64 && y >= 0 && y < height as i64
+    }
+
+    pub fn buffer_world_object(
+        &mut self,
+        shape_id: u64,
+        obj: &dyn WorldObject,
+        camera: &dyn Camera,
+        lights: &[Box<dyn Light>],
+        frame: u64,
+    ) {
+        let render_mode = obj.render_mode().unwrap_or(self.render_mode);
+        let vertices = obj.vectices();
+        let vertex_style = obj.vertex_style();
+        let edge_style = obj.edge_style();
+        let line_glyphs = obj.line_glyphs();
+        let edge_ramp = obj.edge_ramp();
+        let hidden_edges = self.hidden_edges;
+        let scale = self.subcell_mode.scale();
+        let scale_y = scale.1;
+        let face_style = obj.face_style();
+        let object_material = obj.material();
+        let shading_mode = obj.shading_mode();
+        let vertex_normals = if !lights.is_empty() && shading_mode == ShadingMode::Smooth {
+            obj.vertex_normals()
+        } else {
+            vec![]
+        };
+
+        let texture = obj.texture();
+        let triangle_uvs = obj.triangle_uvs();
+
+        let (near, far) = camera.get_clip_planes();
+        let depth = |point: Vector3| camera.get_depth(point);
+        let viewport = self.get_screen_size();
+
+        // Render vertices, edges, then faces. Everything is clipped against the near and far
+        // planes before it is projected, since points behind the observer can't be projected.
+
+        if render_mode.draws_vertices() {
+            for vertex in &vertices {
+                let vertex_depth = depth(*vertex);
+
+                if vertex_depth < near || far.is_some_and(|far| vertex_depth > far) {
+                    continue;
+                }
+
+                let projection = camera.project_vector(*vertex);
+                let (x, y) = (projection.x.round() as i64, projection.y.round() as i64);
+                if self.is_in_bounds(x, y) {
+                    self.plot_character(
+                        x as u16,
+                        y as u16,
+                        projection.z,
+                        vertex_style,
+                        shape_id,
+                        DrawType::Vertex,
+                        frame,
+                    );
+                }
+            }
+        }
+
+        let edges = if render_mode.draws_edges() {
+            let mut edges = obj.edges();
+
+            if let Some(feature_edges) = obj.feature_edges() {
+                edges.extend(feature_edges.extract(&vertices, &obj.triangles(), |point| {
+                    camera.get_view_direction(point)
+                }));
+            }
+
+            edges
+        } else {
+            vec![]
+        };
+
+        for edge in edges {
+            let line = (vertices[edge.0], vertices[edge.1]);
+            let Some((start, end)) = clip_line_to_depth(line, depth, near, far) else {
+                continue;
+            };
+
+            let (start, end) = (camera.project_vector(start), camera.project_vector(end));
+            let direction = (end.x - start.x, end.y - start.y);
+
+            if let Some(ramp) = &edge_ramp {
+                // Coverage is found per cell rather than per pixel, since the ramp picks the
+                // character of the whole cell
+                let to_cell = |point: Vector3| {
+                    vector3!(
+                        (point.x - (scale.0 - 1) as f64 / 2.0) / scale.0 as f64,
+                        (point.y - (scale.1 - 1) as f64 / 2.0) / scale.1 as f64,
+                        point.z
+                    )
+                };
+                let base = match line_glyphs.glyph(direction, false) {
+                    Some(glyph) => (glyph, edge_style.1, edge_style.2),
+                    None => edge_style,
+                };
+                // Cells come in pairs across the line, one pair per step along it
+                let mut cell_count = 0;
+
+                wu_line_3d(
+                    to_cell(start),
+                    to_cell(end),
+                    self.get_size(),
+                    |cell: (i64, i64), depth: f64, coverage: f64| {
+                        let step = cell_count / 2;
+                        cell_count += 1;
+
+                        if coverage <= 0.0 {
+                            return;
+                        }
+
+                        let style = ramp.pick(coverage, base);
+                        let hidden_style = hidden_edges.hide(style, step);
+
+                        for dy in 0..scale.1 as i64 {
+                            for dx in 0..scale.0 as i64 {
+                                let (x, y) =
+                                    (cell.0 * scale.0 as i64 + dx, cell.1 * scale.1 as i64 + dy);
+
+                                if self.is_in_bounds(x, y) {
+                                    self.plot_edge(
+                                        x as u16,
+                                        y as u16,
+                                        depth,
+                                        style,
+                                        hidden_style,
+                                        shape_id,
+                                        frame,
+                                    );
+                                }
+                            }
+                        }
+                    },
+                );
+                continue;
+            }
+
+            let mut step = 0;
+
+            bresenham_line_3d(start, end, viewport, |pixel: (i64, i64), depth: f64| {
+                if self.is_in_bounds(pixel.0, pixel.1) {
+                    let is_lower_half = pixel.1 as u16 % scale_y >= scale_y / 2;
+                    let style = match line_glyphs.glyph(direction, is_lower_half) {
+                        Some(glyph) => (glyph, edge_style.1, edge_style.2),
+                        None => edge_style,
+                    };
+
+                    self.plot_edge(
+                        pixel.0 as u16,
+                        pixel.1 as u16,
+                        depth,
+                        style,
+                        hidden_edges.hide(style, step),
+                        shape_id,
+                        frame,
+                    );
+                }
+
+                step += 1;
+            });
+        }
+
+        let triangles = if render_mode.draws_faces() || render_mode.hides_with_faces() {
+            obj.triangles()
+        } else {
+            vec![]
+        };
+
+        for (index, triangle) in triangles.into_iter().enumerate() {
+            let face_material = obj.face_material(index);
+            let material = face_material.as_ref().unwrap_or(&object_material);
+            let face_style = (face_style.0, material.color, face_style.2);
+            let (texture, uvs) = match (texture, triangle_uvs.get(index)) {
+                (Some(texture), Some(&uvs)) => (Some(texture), uvs),
+                _ => (None, ((0.0, 0.0), (0.0, 0.0), (0.0, 0.0))),
+            };
+            // Light is calculated at each vertex, and interpolated across the face. With flat
+            // shading, every vertex gets the light falling on the face itself. The light of each
+            // light is kept apart, since whether a pixel is in the shadow of a light is decided
+            // for each pixel.
+            let (a, b, c) = (
+                vertices[triangle.0],
+                vertices[triangle.1],
+                vertices[triangle.2],
+            );
+            let reflect = |point, normal, light: &dyn Light| {
+                material.reflect(point, normal, camera.get_view_direction(point), light)
+            };
+            let reflected: Vec<(Illumination, Illumination, Illumination)> = lights
+                .iter()
+                .map(|light| match shading_mode {
+                    ShadingMode::Flat => {
+                        let centroid = (a + b + c) / 3.0;
+                        let illumination = reflect(centroid, face_normal(a, b, c), light.as_ref());
+
+                        (illumination, illumination, illumination)
+                    }
+                    ShadingMode::Smooth => (
+                        reflect(a, vertex_normals[triangle.0], light.as_ref()),
+                        reflect(b, vertex_normals[triangle.1], light.as_ref()),
+                        reflect(c, vertex_normals[triangle.2], light.as_ref()),
+                    ),
+                })
+                .collect();
+            let face_vertex = |position, corners, uv| FaceVertex {
+                position,
+                corners,
+                uv,
+            };
+            let triangle = (
+                face_vertex(a, (1.0, 0.0, 0.0), uvs.0),
+                face_vertex(b, (0.0, 1.0, 0.0), uvs.1),
+                face_vertex(c, (0.0, 0.0, 1.0), uvs.2),
+            );
+
+            for clipped in
+                clip_triangle_to_depth(triangle, |vertex| depth(vertex.position), near, far)
+            {
+                let points = (
+                    camera.project_vector(clipped.0.position),
+                    camera.project_vector(clipped.1.position),
+                    camera.project_vector(clipped.2.position),
+                );
+
+                // Check direction, perform culling if needed
+                let direction = ((points.1 - points.0).with_z(0.0)
+                    * (points.2 - points.0).with_z(0.0))
+                .normalize();
+
+                if direction == vector3!(0, 0, -1) {
+                    let slope_bias = self.depth_bias.face_slope * depth_slope(points);
+
+                    edge_function_triangle_3d(points, viewport, |pixel, depth, weights| {
+                        if !self.is_in_bounds(pixel.0, pixel.1) {
+                            return;
+                        }
+
+                        if !render_mode.draws_faces() {
+                            self.plot_character(
+                                pixel.0 as u16,
+                                pixel.1 as u16,
+                                depth + slope_bias,
+                                face_style,
+                                shape_id,
+                                DrawType::Occluder,
+                                frame,
+                            );
+                            return;
+                        }
+
+                        // Screen space weights don't interpolate evenly across faces seen in
+                        // perspective
+                        let weights = if camera.is_orthographic() {
+                            weights
+                        } else {
+                            perspective_correct(weights, (points.0.z, points.1.z, points.2.z))
+                        };
+                        let vertex = FaceVertex::blend(clipped, weights);
+                        let illumination = material.combine(
+                            lights
+                                .iter()
+                                .zip(&reflected)
+                                .filter(|(light, _)| !light.is_shadowed(vertex.position))
+                                .map(|(_, &corners)| Illumination::blend(corners, vertex.corners)),
+                        );
+                        let style = match texture {
+                            // Textured faces keep their characters, and are only tinted by light
+                            Some(texture) => {
+                                let style = texture.sample(vertex.uv);
+
+                                if lights.is_empty() {
+                                    style
+                                } else {
+                                    (style.0, illumination.tint(style.1), style.2)
+                                }
+                            }
+                            None if lights.is_empty() => face_style,
+                            None => material.ramp.shade(illumination, face_style),
+                        };
+
+                        self.plot_character(
+                            pixel.0 as u16,
+                            pixel.1 as u16,
+                            depth + slope_bias,
+                            style,
+                            shape_id,
+                            DrawType::Face,
+                            frame,
+                        );
+                    });
+                }
+            }
+        }
+    }
+
+    /// Combine the pixels covered by the cell at (`col`, `row`) into a single cell
+    pub fn get_cell(&self, col: usize, row: usize) -> Cell {
+        let (scale_x, scale_y) = self.subcell_mode.scale();
+        let pixel_width = self.get_screen_size().0 as usize;
+        let pixels: Vec<&Pixel> = (0..scale_y as usize)
+            .flat_map(|y| {
+                (0..scale_x as usize).map(move |x| {
+                    &self.pixels
+                        [(row * scale_y as usize + y) * pixel_width + col * scale_x as usize + x]
+                })
+            })
+            .collect();
+
+        let styles: Vec<Option<Style>> = pixels.iter().map(|pixel| pixel.get_style()).collect();
+
+        let mask = styles
+            .iter()
+            .enumerate()
+            .filter(|(_, style)| style.is_some())
+            .fold(0u8, |mask, (bit, _)| mask | 1 << bit);
+        // The closest pixel decides the color of the cell
+        let closest = pixels
+            .iter()
+            .zip(&styles)
+            .filter_map(|(pixel, style)| style.map(|style| (pixel, style)))
+            .min_by(|(a, _), (b, _)| {
+                a.dist
+                    .total_cmp(&b.dist)
+                    .then(a.draw_type.partial_cmp(&b.draw_type).unwrap())
+            });
+
+        let Some((_, style)) = closest else {
+            return Cell::blank();
+        };
+
+        let cell = Cell {
+            character: self.subcell_mode.glyph(mask),
+            color: style.1,
+            background: Color::Reset,
+            decor: style.2,
+        };
+
+        match self.subcell_mode {
+            SubCellMode::Character => Cell {
+                character: style.0,
+                ..cell
+            },
+            SubCellMode::HalfBlock if mask == 0b11 => {
+                let (top, bottom) = (styles[0].unwrap().1, styles[1].unwrap().1);
+
+                if top == bottom {
+                    cell
+                } else if bottom != Color::Reset {
+                    // Top pixel in the foreground, bottom pixel in the background
+                    Cell {
+                        character: '▀',
+                        color: top,
+                        background: bottom,
+                        ..cell
+                    }
+                } else {
+                    // The background can't be set to the default foreground color, so flip
+                    Cell {
+                        character: '▄',
+                        color: bottom,
+                        background: top,
+                        ..cell
+                    }
+                }
+            }
+            _ => cell,
+        }
+    }
+
+    /// All cells, row by row
+    pub fn get_cells(&self) -> Vec<Cell> {
+        let mut cells = Vec::with_capacity(self.width as usize * self.height as usize);
+
+        for row in 0..self.height as usize {
+            for col in 0..self.width as usize {
+                cells.push(self.get_cell(col, row));
+            }
+        }
+
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        camera::IsoCamera,
+        lighting::{COVERAGE_RAMP, DirectionalLight, Ramp},
+        shapes::RotatingSquare,
+        target::text::frame_to_text,
+    };
+
+    /// Single edge, anti-aliased with the ramp if it has one
+    struct Line(Vector3, Vector3, Option<Ramp>);
+
+    impl WorldObject for Line {
+        fn vectices(&self) -> Vec<Vector3> {
+            vec![self.0, self.1]
+        }
+
+        fn edges(&self) -> Vec<(usize, usize)> {
+            vec![(0, 1)]
+        }
+
+        fn edge_ramp(&self) -> Option<Ramp> {
+            self.2.clone()
+        }
+    }
+
+    /// Light shining straight at the camera, which is blocked on the positive (true) or negative
+    /// side of the plane x = 0
+    struct HalfShadowed(bool);
+
+    impl Light for HalfShadowed {
+        fn get_color(&self) -> Color {
+            Color::Reset
+        }
+
+        fn get_intensity(&self) -> f64 {
+            0.5
+        }
+
+        fn get_direction(&self, _point: Vector3) -> Option<Vector3> {
+            Some(vector3!(0, 0, -1))
+        }
+
+        fn casts_shadows(&self) -> bool {
+            true
+        }
+
+        fn is_shadowed(&self, point: Vector3) -> bool {
+            (point.x >= 0.0) == self.0
+        }
+    }
+
+    #[test]
+    fn depth_bias() {
+        let mut framebuffer = Framebuffer::new((8, 4), SubCellMode::Character);
+        let camera = IsoCamera::default(framebuffer.get_screen_size());
+        let square = RotatingSquare::new(Vector3::zero(), 4);
+
+        // Edges and vertices are in front of the faces they border
+        framebuffer.buffer_world_object(0, &square, &camera, &[], 1);
+        assert_eq!(frame_to_text(&framebuffer), "\n  XOOOX\n  O...O\n  XOOOX\n");
+
+        // Unless the faces are biased towards the camera. Faces leave out their right and bottom
+        // edges, so the edges still show there.
+        framebuffer.clear();
+        framebuffer.set_depth_bias(DepthBias {
+            face: -1.0,
+            ..DepthBias::default()
+        });
+        framebuffer.buffer_world_object(0, &square, &camera, &[], 1);
+        assert_eq!(frame_to_text(&framebuffer), "\n  ....X\n  ....O\n  XOOOX\n");
+    }
+
+    #[test]
+    fn anti_aliased_edges() {
+        let mut framebuffer = Framebuffer::new((8, 4), SubCellMode::Character);
+        let camera = IsoCamera::default(framebuffer.get_screen_size());
+        let line = Line(
+            vector3!(-3, 0.5, 0),
+            vector3!(3, 0.5, 0),
+            Some(Ramp::new(COVERAGE_RAMP)),
+        );
+
+        // The line runs between two rows of cells, so it covers half of each, and only a quarter
+        // of the cells at its ends
+        framebuffer.buffer_world_object(0, &line, &camera, &[], 1);
+        assert_eq!(frame_to_text(&framebuffer), "\n :+++++:\n X+++++X\n\n");
+    }
+
+    #[test]
+    fn render_modes() {
+        let mut framebuffer = Framebuffer::new((8, 4), SubCellMode::Character);
+        let camera = IsoCamera::default(framebuffer.get_screen_size());
+        let square = RotatingSquare::new(Vector3::zero(), 4);
+        let render = |framebuffer: &mut Framebuffer, render_mode| {
+            framebuffer.clear();
+            framebuffer.set_render_mode(render_mode);
+            framebuffer.buffer_world_object(0, &square, &camera, &[], 1);
+
+            frame_to_text(framebuffer)
+        };
+
+        assert_eq!(
+            render(&mut framebuffer, RenderMode::Points),
+            "\n  X   X\n\n  X   X\n"
+        );
+        assert_eq!(
+            render(&mut framebuffer, RenderMode::Wireframe),
+            "\n  XOOOX\n  O   O\n  XOOOX\n"
+        );
+        assert_eq!(
+            render(&mut framebuffer, RenderMode::Solid),
+            "\n  ....\n  ....\n\n"
+        );
+        assert_eq!(
+            render(&mut framebuffer, RenderMode::HiddenLine),
+            "\n  XOOOX\n  O   O\n  XOOOX\n"
+        );
+
+        // Faces hide edges behind them without being drawn
+        let line = Line(vector3!(-4, 0, -1), vector3!(4, 0, -1), None);
+        framebuffer.buffer_world_object(1, &line, &camera, &[], 1);
+        assert_eq!(
+            frame_to_text(&framebuffer),
+            "\n  XOOOX\nXOO   OO\n  XOOOX\n"
+        );
+    }
+
+    #[test]
+    fn hidden_edges() {
+        let mut framebuffer = Framebuffer::new((8, 4), SubCellMode::Character);
+        let camera = IsoCamera::default(framebuffer.get_screen_size());
+        let square = RotatingSquare::new(Vector3::zero(), 4);
+        let line = Line(vector3!(-4, 0, -1), vector3!(4, 0, -1), None);
+
+        // Hidden parts of the line are dashed, whether the faces hiding them are drawn or not
+        framebuffer.set_hidden_edges(Stipple::dashed(1, 1));
+        framebuffer.buffer_world_object(0, &square, &camera, &[], 1);
+        framebuffer.buffer_world_object(1, &line, &camera, &[], 1);
+        assert_eq!(
+            frame_to_text(&framebuffer),
+            "\n  XOOOX\nXOO.O.OO\n  XOOOX\n"
+        );
+
+        framebuffer.clear();
+        framebuffer.set_render_mode(RenderMode::HiddenLine);
+        framebuffer.buffer_world_object(1, &line, &camera, &[], 1);
+        framebuffer.buffer_world_object(0, &square, &camera, &[], 1);
+        assert_eq!(
+            frame_to_text(&framebuffer),
+            "\n  XOOOX\nXOO O OO\n  XOOOX\n"
+        );
+    }
+
+    #[test]
+    fn shadows_per_light() {
+        let mut framebuffer = Framebuffer::new((8, 4), SubCellMode::Character);
+        let camera = IsoCamera::default(framebuffer.get_screen_size());
+        let square = RotatingSquare::new(Vector3::zero(), 4);
+        let render = |framebuffer: &mut Framebuffer, lights: &[Box<dyn Light>]| {
+            framebuffer.clear();
+            framebuffer.set_render_mode(RenderMode::Solid);
+            framebuffer.buffer_world_object(0, &square, &camera, lights, 1);
+
+            frame_to_text(framebuffer)
+        };
+        let unshadowed = render(
+            &mut framebuffer,
+            &[Box::new(DirectionalLight::new(
+                vector3!(0, 0, -1),
+                Color::Reset,
+                0.5,
+            ))],
+        );
+
+        // Each half of the square is only hidden from one of the lights, so it is lit by the other
+        assert_eq!(unshadowed, "\n  ====\n  ====\n\n");
+        assert_eq!(
+            render(
+                &mut framebuffer,
+                &[Box::new(HalfShadowed(false)), Box::new(HalfShadowed(true))]
+            ),
+            unshadowed
+        );
+    }
+
+    #[test]
+    fn limit_size() {
+        let mut framebuffer = Framebuffer::new((100, 20000), SubCellMode::Braille);
+
+        // Braille cells are 4 pixels tall, so only 16383 rows fit
+        assert_eq!(framebuffer.get_size(), (100, 16383));
+        assert_eq!(framebuffer.get_screen_size(), (200, 65532));
+        assert_eq!(framebuffer.get_cell(99, 16382), Cell::blank());
+
+        framebuffer.resize((40000, 10));
+        assert_eq!(framebuffer.get_size(), (32767, 10));
+    }
+}
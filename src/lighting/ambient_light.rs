@@ -0,0 +1,27 @@
+use crate::{lighting::Light, terminal::Color, vector3::Vector3};
+
+/// Light falling evenly on every surface, from every direction
+pub struct AmbientLight {
+    color: Color,
+    intensity: f64,
+}
+
+impl AmbientLight {
+    pub fn new(color: Color, intensity: f64) -> AmbientLight {
+        AmbientLight { color, intensity }
+    }
+}
+
+impl Light for AmbientLight {
+    fn get_color(&self) -> Color {
+        self.color
+    }
+
+    fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    fn get_direction(&self, _point: Vector3) -> Option<Vector3> {
+        None
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    lighting::{Light, ShadowMap},
+    terminal::Color,
+    vector3::Vector3,
+    world_object::WorldObject,
+};
+
+/// Light shining in a single direction from infinitely far away, like the sun
+pub struct DirectionalLight {
+    /// Direction the light travels in
+    direction: Vector3,
+    color: Color,
+    intensity: f64,
+    /// None if the light doesn't cast shadows
+    shadow_map: Option<ShadowMap>,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Vector3, color: Color, intensity: f64) -> DirectionalLight {
+        DirectionalLight {
+            direction: direction.normalize(),
+            color,
+            intensity,
+            shadow_map: None,
+        }
+    }
+
+    /// Cast shadows, using a shadow map `resolution` pixels wide and high
+    pub fn with_shadows(self, resolution: u16) -> DirectionalLight {
+        DirectionalLight {
+            shadow_map: Some(ShadowMap::new(resolution)),
+            ..self
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn get_color(&self) -> Color {
+        self.color
+    }
+
+    fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    fn get_direction(&self, _point: Vector3) -> Option<Vector3> {
+        Some(self.direction)
+    }
+
+    fn casts_shadows(&self) -> bool {
+        self.shadow_map.is_some()
+    }
+
+    fn update_shadows(&mut self, objects: &[&dyn WorldObject]) {
+        if let Some(shadow_map) = &mut self.shadow_map {
+            shadow_map.update(self.direction, objects);
+        }
+    }
+
+    fn is_shadowed(&self, point: Vector3) -> bool {
+        self.shadow_map
+            .as_ref()
+            .is_some_and(|shadow_map| shadow_map.is_shadowed(point))
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    lighting::{Illumination, Light, Ramp},
+    terminal::Color,
+    vector3::Vector3,
+};
+
+/// How a surface reacts to light, using the Phong reflection model
+#[derive(Clone, PartialEq, Debug)]
+pub struct Material {
+    /// Share of ambient light reflected
+    pub ambient: f64,
+    /// Share of direct light scattered evenly in every direction
+    pub diffuse: f64,
+    /// Share of direct light reflected like a mirror, which shows up as highlights
+    pub specular: f64,
+    /// How tight the highlights are. Higher values give smaller, sharper highlights.
+    pub shininess: f64,
+    pub color: Color,
+    /// Emissive surfaces give off their own light, so they are always drawn fully lit
+    pub emissive: bool,
+    pub ramp: Ramp,
+}
+
+impl Material {
+    /// Rough surface without highlights
+    pub fn matte(color: Color) -> Material {
+        Material {
+            color,
+            ..Material::default()
+        }
+    }
+
+    /// Smooth surface with sharp highlights
+    pub fn glossy(color: Color) -> Material {
+        Material {
+            diffuse: 0.7,
+            specular: 0.6,
+            shininess: 32.0,
+            color,
+            ..Material::default()
+        }
+    }
+
+    /// Light reflected towards the viewer by a surface at `point` with the (normalized) `normal`.
+    /// `view` is the (normalized) direction from the surface towards the viewer. Lights for which
+    /// `is_shadowed` is true don't reach the surface. The intensity is clamped to [0, 1].
+    pub fn illuminate(
+        &self,
+        point: Vector3,
+        normal: Vector3,
+        view: Vector3,
+        lights: &[Box<dyn Light>],
+        is_shadowed: impl Fn(&dyn Light) -> bool,
+    ) -> Illumination {
+        self.combine(
+            lights
+                .iter()
+                .filter(|light| !is_shadowed(light.as_ref()))
+                .map(|light| self.reflect(point, normal, view, light.as_ref())),
+        )
+    }
+
+    /// Light from a single `light` reflected towards the viewer, without clamping, so the light
+    /// of several lights can be added up by `combine`
+    pub fn reflect(
+        &self,
+        point: Vector3,
+        normal: Vector3,
+        view: Vector3,
+        light: &dyn Light,
+    ) -> Illumination {
+        let reflected = match light.get_direction(point) {
+            Some(direction) => {
+                let diffuse = f64::max(0.0, -normal.dot(direction));
+                // Only surfaces facing the light have highlights
+                let specular = if diffuse > 0.0 {
+                    let reflection = direction - normal * (2.0 * direction.dot(normal));
+
+                    f64::max(0.0, reflection.dot(view)).powf(self.shininess)
+                } else {
+                    0.0
+                };
+
+                self.diffuse * diffuse + self.specular * specular
+            }
+            None => self.ambient,
+        };
+        let amount = reflected * light.get_intensity() * light.get_attenuation(point);
+        let (r, g, b) = light.get_color().to_rgb().unwrap_or((255, 255, 255));
+
+        Illumination {
+            intensity: amount,
+            color: (
+                amount * r as f64 / 255.0,
+                amount * g as f64 / 255.0,
+                amount * b as f64 / 255.0,
+            ),
+        }
+    }
+
+    /// Add up the light reflected from each light, clamping the intensity to [0, 1]
+    pub fn combine(&self, reflected: impl Iterator<Item = Illumination>) -> Illumination {
+        if self.emissive {
+            return Illumination {
+                intensity: 1.0,
+                color: (1.0, 1.0, 1.0),
+            };
+        }
+
+        let mut illumination = Illumination::dark();
+
+        for light in reflected {
+            illumination.intensity += light.intensity;
+            illumination.color.0 += light.color.0;
+            illumination.color.1 += light.color.1;
+            illumination.color.2 += light.color.2;
+        }
+
+        illumination.intensity = illumination.intensity.clamp(0.0, 1.0);
+
+        illumination
+    }
+}
+
+impl Default for Material {
+    /// Reflects all diffuse and ambient light, without highlights
+    fn default() -> Material {
+        Material {
+            ambient: 1.0,
+            diffuse: 1.0,
+            specular: 0.0,
+            shininess: 1.0,
+            color: Color::Reset,
+            emissive: false,
+            ramp: Ramp::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lighting::{AmbientLight, DirectionalLight, PointLight},
+        vector3,
+    };
+
+    #[test]
+    fn light_surfaces() {
+        let lights: Vec<Box<dyn Light>> = vec![
+            Box::new(DirectionalLight::new(vector3!(0, 0, -1), Color::Reset, 0.5)),
+            Box::new(PointLight::new(
+                vector3!(0, 10, 0),
+                Color::Rgb(255, 0, 0),
+                1.0,
+                (0.0, 0.1, 0.0),
+            )),
+            Box::new(AmbientLight::new(Color::Rgb(0, 0, 255), 0.25)),
+        ];
+        let material = Material::default();
+        let view = vector3!(0, 0, 1);
+        let facing_directional =
+            material.illuminate(Vector3::zero(), view, view, &lights, |_| false);
+        let facing_point =
+            material.illuminate(Vector3::zero(), vector3!(0, 1, 0), view, &lights, |_| false);
+
+        assert_eq!(facing_directional.intensity, 0.75);
+        assert_eq!(facing_directional.color, (0.5, 0.5, 0.75));
+        // The point light is 10 away, so it adds 1 / (0.1 * 10) = 1
+        assert_eq!(facing_point.intensity, 1.0);
+        assert_eq!(facing_point.color, (1.0, 0.0, 0.25));
+        assert_eq!(facing_point.tint(Color::Reset), Color::Rgb(255, 0, 64));
+    }
+
+    #[test]
+    fn highlights() {
+        let lights: Vec<Box<dyn Light>> = vec![Box::new(DirectionalLight::new(
+            vector3!(1, 0, -1),
+            Color::Reset,
+            1.0,
+        ))];
+        let (matte, glossy) = (Material::matte(Color::Red), Material::glossy(Color::Red));
+        let normal = vector3!(0, 0, 1);
+        // Looking along the reflection of the light, and away from it
+        let (mirror, away) = (
+            vector3!(1, 0, 1).normalize(),
+            vector3!(-1, 0, 1).normalize(),
+        );
+        let lit = |material: &Material, view| {
+            material
+                .illuminate(Vector3::zero(), normal, view, &lights, |_| false)
+                .intensity
+        };
+
+        assert_eq!(lit(&matte, mirror), lit(&matte, away));
+        assert!(lit(&glossy, mirror) > lit(&matte, mirror));
+        assert!(lit(&glossy, away) < lit(&matte, away));
+        assert_eq!(
+            Material {
+                emissive: true,
+                ..Material::default()
+            }
+            .illuminate(Vector3::zero(), -normal, away, &lights, |_| false)
+            .intensity,
+            1.0
+        );
+    }
+}
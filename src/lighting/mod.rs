@@ -0,0 +1,272 @@
+pub mod ambient_light;
+pub mod directional_light;
+pub mod material;
+pub mod point_light;
+pub mod shadow_map;
+
+pub use crate::lighting::{
+    ambient_light::AmbientLight, directional_light::DirectionalLight, material::Material,
+    point_light::PointLight, shadow_map::ShadowMap,
+};
+
+use crate::{
+    clip::Interpolate,
+    terminal::{Color, Style},
+    vector3::Vector3,
+    world_object::WorldObject,
+};
+
+/// Characters ordered from darkest to brightest
+pub const LUMINANCE_RAMP: &str = ".,-~:;=!*#$@";
+
+/// Characters ordered from lightest to heaviest, for edges that barely touch a cell up to edges
+/// that cover it
+pub const COVERAGE_RAMP: &str = ".:+#";
+
+/// Source of light in the world
+pub trait Light {
+    fn get_color(&self) -> Color;
+    /// Brightness, where 1 fully lights a surface facing the light
+    fn get_intensity(&self) -> f64;
+    /// Direction the light travels in when it reaches `point`, or None if it comes from every
+    /// direction
+    fn get_direction(&self, point: Vector3) -> Option<Vector3>;
+
+    /// Share of the light's intensity that reaches `point`
+    fn get_attenuation(&self, _point: Vector3) -> f64 {
+        1.0
+    }
+
+    /// Whether objects block the light and cast shadows
+    fn casts_shadows(&self) -> bool {
+        false
+    }
+
+    /// Find out where `objects` block the light, after they have moved. Only called if the light
+    /// casts shadows.
+    fn update_shadows(&mut self, _objects: &[&dyn WorldObject]) {}
+
+    /// Whether `point` is hidden from the light by an object
+    fn is_shadowed(&self, _point: Vector3) -> bool {
+        false
+    }
+
+    fn update(&mut self, _frame: u64) {}
+}
+
+/// Light falling on a surface
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Illumination {
+    /// Brightness, from 0 to 1
+    pub intensity: f64,
+    /// Sum of the colors of the lights, as RGB from 0 to 1, weighted by how much light each adds
+    pub color: (f64, f64, f64),
+}
+
+impl Illumination {
+    pub fn dark() -> Illumination {
+        Illumination {
+            intensity: 0.0,
+            color: (0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Combine the illumination of the vertices of a triangle, with the barycentric `weights` of
+    /// a point inside it
+    pub fn blend(
+        vertices: (Illumination, Illumination, Illumination),
+        weights: (f64, f64, f64),
+    ) -> Illumination {
+        let blend = |a: f64, b: f64, c: f64| a * weights.0 + b * weights.1 + c * weights.2;
+        let (a, b, c) = (vertices.0.color, vertices.1.color, vertices.2.color);
+
+        Illumination {
+            intensity: blend(
+                vertices.0.intensity,
+                vertices.1.intensity,
+                vertices.2.intensity,
+            ),
+            color: (
+                blend(a.0, b.0, c.0),
+                blend(a.1, b.1, c.1),
+                blend(a.2, b.2, c.2),
+            ),
+        }
+    }
+
+    /// Tint `color` with the hue of the light. White light leaves the color unchanged.
+    pub fn tint(&self, color: Color) -> Color {
+        let (r, g, b) = self.color;
+        let brightest = r.max(g).max(b);
+
+        if brightest <= 0.0 || r.min(g).min(b) / brightest > 0.999 {
+            return color;
+        }
+
+        let (base_r, base_g, base_b) = color.to_rgb().unwrap_or((255, 255, 255));
+        let channel = |base: u8, light: f64| (base as f64 * light / brightest).round() as u8;
+
+        Color::Rgb(channel(base_r, r), channel(base_g, g), channel(base_b, b))
+    }
+}
+
+impl Interpolate for Illumination {
+    fn interpolate(self, other: Illumination, t: f64) -> Illumination {
+        Illumination::blend((self, other, other), (1.0 - t, t, 0.0))
+    }
+}
+
+/// How light is spread across the faces of an object
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShadingMode {
+    /// Each face is lit evenly, by the light falling on the face itself
+    Flat,
+    /// Light is calculated at each vertex and interpolated across faces (Gouraud shading), so
+    /// curved surfaces look smooth
+    Smooth,
+}
+
+/// Normal of the triangle `a`, `b`, `c`, pointing towards the side from which its vertices are in
+/// counterclockwise order
+pub fn face_normal(a: Vector3, b: Vector3, c: Vector3) -> Vector3 {
+    ((b - a) * (c - a)).normalize()
+}
+
+/// Normal of each vertex, found by averaging the normals of the faces around it, weighted by the
+/// area of each face. Vertices that are not part of any face get a zero normal.
+pub fn average_normals(vertices: &[Vector3], triangles: &[(usize, usize, usize)]) -> Vec<Vector3> {
+    let mut normals = vec![Vector3::zero(); vertices.len()];
+
+    for &(a, b, c) in triangles {
+        // The length of the cross product is twice the area of the triangle
+        let weighted_normal = (vertices[b] - vertices[a]) * (vertices[c] - vertices[a]);
+
+        normals[a] += weighted_normal;
+        normals[b] += weighted_normal;
+        normals[c] += weighted_normal;
+    }
+
+    normals
+        .into_iter()
+        .map(|normal| {
+            if normal.is_zero() {
+                normal
+            } else {
+                normal.normalize()
+            }
+        })
+        .collect()
+}
+
+/// Maps light intensity onto characters, and optionally colors, ordered from darkest to brightest
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ramp {
+    characters: Vec<char>,
+    colors: Vec<Color>,
+}
+
+impl Ramp {
+    pub fn new(characters: &str) -> Ramp {
+        Ramp {
+            characters: characters.chars().collect(),
+            colors: vec![],
+        }
+    }
+
+    /// Also shade colors, instead of keeping the color of the base style
+    pub fn with_colors(self, colors: Vec<Color>) -> Ramp {
+        Ramp { colors, ..self }
+    }
+
+    /// Shade `base` with the light falling on it. Without a color ramp, the base color is tinted
+    /// by the color of the light. Parts of the style the ramp doesn't cover are kept.
+    pub fn shade(&self, illumination: Illumination, base: Style) -> Style {
+        let style = self.pick(illumination.intensity, base);
+
+        if self.colors.is_empty() {
+            (style.0, illumination.tint(style.1), style.2)
+        } else {
+            style
+        }
+    }
+
+    /// Entry for `value`, from 0 to 1, on top of `base`. Parts of the style the ramp doesn't cover
+    /// are kept.
+    pub fn pick(&self, value: f64, base: Style) -> Style {
+        let value = value.clamp(0.0, 1.0);
+        let index = |len: usize| (value * (len - 1) as f64).round() as usize;
+        let character = if self.characters.is_empty() {
+            base.0
+        } else {
+            self.characters[index(self.characters.len())]
+        };
+        let color = if self.colors.is_empty() {
+            base.1
+        } else {
+            self.colors[index(self.colors.len())]
+        };
+
+        (character, color, base.2)
+    }
+}
+
+impl Default for Ramp {
+    fn default() -> Ramp {
+        Ramp::new(LUMINANCE_RAMP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{terminal::Decor, vector3};
+
+    fn white(intensity: f64) -> Illumination {
+        Illumination {
+            intensity,
+            color: (intensity, intensity, intensity),
+        }
+    }
+
+    #[test]
+    fn shade_faces() {
+        let ramp = Ramp::default().with_colors(vec![Color::Blue, Color::Cyan, Color::White]);
+        let base = ('.', Color::Reset, Decor::Bold);
+
+        assert_eq!(
+            ramp.shade(white(0.0), base),
+            ('.', Color::Blue, Decor::Bold)
+        );
+        assert_eq!(
+            ramp.shade(white(0.5), base),
+            ('=', Color::Cyan, Decor::Bold)
+        );
+        assert_eq!(
+            ramp.shade(white(1.0), base),
+            ('@', Color::White, Decor::Bold)
+        );
+        // Without a color ramp, the base color is only changed by colored light
+        assert_eq!(
+            Ramp::default().shade(white(1.0), base),
+            ('@', Color::Reset, Decor::Bold)
+        );
+    }
+
+    #[test]
+    fn vertex_normals() {
+        // Two faces of a cube, meeting along the edge from vertex 1 to vertex 2
+        let vertices = [
+            vector3!(0, 0, 1),
+            vector3!(1, 0, 1),
+            vector3!(1, 1, 1),
+            vector3!(1, 0, 0),
+        ];
+        let normals = average_normals(&vertices, &[(0, 1, 2), (1, 3, 2)]);
+        let diagonal = vector3!(1, 0, 1).normalize();
+
+        assert_eq!(normals[0], vector3!(0, 0, 1));
+        assert!((normals[1] - diagonal).is_zero());
+        assert!((normals[2] - diagonal).is_zero());
+        assert_eq!(normals[3], vector3!(1, 0, 0));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{lighting::Light, terminal::Color, vector3::Vector3};
+
+/// Light shining in every direction from a single point, like a light bulb
+pub struct PointLight {
+    position: Vector3,
+    color: Color,
+    intensity: f64,
+    /// Constant, linear and quadratic attenuation factors. The light reaching a point `d` away is
+    /// `intensity / (constant + linear * d + quadratic * d^2)`.
+    attenuation: (f64, f64, f64),
+}
+
+impl PointLight {
+    pub fn new(
+        position: Vector3,
+        color: Color,
+        intensity: f64,
+        attenuation: (f64, f64, f64),
+    ) -> PointLight {
+        PointLight {
+            position,
+            color,
+            intensity,
+            attenuation,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn get_color(&self) -> Color {
+        self.color
+    }
+
+    fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    fn get_direction(&self, point: Vector3) -> Option<Vector3> {
+        Some((point - self.position).normalize())
+    }
+
+    fn get_attenuation(&self, point: Vector3) -> f64 {
+        let distance = point.distance_to(self.position);
+        let (constant, linear, quadratic) = self.attenuation;
+
+        1.0 / (constant + linear * distance + quadratic * distance * distance)
+    }
+}
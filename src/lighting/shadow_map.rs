@@ -0,0 +1,135 @@
+use crate::{
+    camera::{Camera, IsoCamera},
+    render::edge_function_triangle_3d,
+    vector3,
+    vector3::Vector3,
+    world_object::WorldObject,
+};
+
+/// Depth of the closest surface seen from a directional light, for each pixel of a square map
+/// covering every object. Points further from the light than the closest surface are in shadow.
+pub struct ShadowMap {
+    /// Width and height of the map, in pixels
+    resolution: u16,
+    /// Looks along the light's direction, None until the map is first updated
+    camera: Option<IsoCamera>,
+    depths: Vec<f64>,
+    /// Distance a point has to be behind the closest surface to be in shadow, so surfaces don't
+    /// shadow themselves
+    bias: f64,
+}
+
+impl ShadowMap {
+    pub fn new(resolution: u16) -> ShadowMap {
+        ShadowMap {
+            resolution,
+            camera: None,
+            depths: vec![f64::INFINITY; resolution as usize * resolution as usize],
+            bias: 0.0,
+        }
+    }
+
+    pub fn get_resolution(&self) -> u16 {
+        self.resolution
+    }
+
+    /// Render the depth of `objects` as seen by light travelling in `direction`
+    pub fn update(&mut self, direction: Vector3, objects: &[&dyn WorldObject]) {
+        let vertices: Vec<Vector3> = objects.iter().flat_map(|obj| obj.vectices()).collect();
+
+        self.depths.fill(f64::INFINITY);
+        if vertices.is_empty() {
+            self.camera = None;
+            return;
+        }
+
+        // Fit the map around a sphere containing every vertex
+        let center = vertices
+            .iter()
+            .fold(Vector3::zero(), |sum, &vertex| sum + vertex)
+            / vertices.len() as f64;
+        let radius = vertices
+            .iter()
+            .map(|vertex| vertex.distance_to(center))
+            .fold(1.0, f64::max);
+        let direction = direction.normalize();
+        let up = if direction.y.abs() < 0.9 {
+            vector3!(0, 1, 0)
+        } else {
+            vector3!(1, 0, 0)
+        };
+        let mut camera = IsoCamera::new(
+            center - direction * (2.0 * radius),
+            direction,
+            (up - up.project(direction)).normalize(),
+            (self.resolution, self.resolution),
+        );
+
+        camera.update_scale(self.resolution as f64 / (2.0 * radius));
+        // Up to two pixels off, from the map's resolution and the rounding of pixel positions
+        self.bias = 2.0 * 2.0 * radius / self.resolution as f64;
+
+        let size = (self.resolution, self.resolution);
+        let resolution = self.resolution as usize;
+
+        for obj in objects {
+            let vertices = obj.vectices();
+
+            for (a, b, c) in obj.triangles() {
+                let points = (
+                    camera.project_vector(vertices[a]),
+                    camera.project_vector(vertices[b]),
+                    camera.project_vector(vertices[c]),
+                );
+
+                // Faces block light whichever way they face
+                edge_function_triangle_3d(points, size, |pixel, depth, _| {
+                    let closest =
+                        &mut self.depths[pixel.1 as usize * resolution + pixel.0 as usize];
+
+                    *closest = closest.min(depth);
+                });
+            }
+        }
+
+        self.camera = Some(camera);
+    }
+
+    /// Whether `point` is hidden from the light by a closer surface
+    pub fn is_shadowed(&self, point: Vector3) -> bool {
+        let Some(camera) = &self.camera else {
+            return false;
+        };
+        let projection = camera.project_vector(point);
+        let (x, y) = (projection.x.round() as i64, projection.y.round() as i64);
+        let resolution = self.resolution as i64;
+
+        if x < 0 || x >= resolution || y < 0 || y >= resolution {
+            return false;
+        }
+
+        projection.z > self.depths[(y * resolution + x) as usize] + self.bias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::RotatingSquare;
+
+    #[test]
+    fn cast_shadows() {
+        let blocker = RotatingSquare::new(vector3!(0, 0, 5), 4);
+        let ground = RotatingSquare::new(Vector3::zero(), 20);
+        let mut shadow_map = ShadowMap::new(64);
+
+        shadow_map.update(vector3!(0, 0, -1), &[&blocker, &ground]);
+
+        assert!(shadow_map.is_shadowed(vector3!(0, 0, 0)));
+        assert!(shadow_map.is_shadowed(vector3!(1.5, -1.5, 0)));
+        assert!(!shadow_map.is_shadowed(vector3!(5, 5, 0)));
+        // Surfaces don't shadow themselves
+        assert!(!shadow_map.is_shadowed(vector3!(0, 0, 5)));
+        assert!(!shadow_map.is_shadowed(vector3!(8, -8, 0)));
+    }
+}
@@ -0,0 +1,56 @@
+use std::f64::consts::PI;
+
+/// How the character of each edge pixel is chosen
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineGlyphs {
+    /// Every pixel uses the character of the edge style
+    Fixed,
+    /// Pick `-`, `_`, `|`, `/` or `\` from the slope of the line
+    Ascii,
+    /// Pick `─`, `│`, `╱` or `╲` from the slope of the line
+    BoxDrawing,
+}
+
+impl LineGlyphs {
+    /// Character for a pixel of a line going `direction` (x, y) across the screen, where y points
+    /// down. `is_lower_half` is whether the pixel is in the bottom half of its cell, so flat lines
+    /// can be drawn with `_` there. None for `Fixed`.
+    pub fn glyph(self, direction: (f64, f64), is_lower_half: bool) -> Option<char> {
+        // Angle of the line from the horizontal, from 0 up to PI, counterclockwise
+        let angle = (-direction.1).atan2(direction.0).rem_euclid(PI);
+        let octant = (angle / (PI / 4.0)).round() as u8 % 4;
+
+        match (self, octant) {
+            (LineGlyphs::Fixed, _) => None,
+            (LineGlyphs::Ascii, 0) if is_lower_half => Some('_'),
+            (LineGlyphs::Ascii, 0) => Some('-'),
+            (LineGlyphs::Ascii, 1) => Some('/'),
+            (LineGlyphs::Ascii, 2) => Some('|'),
+            (LineGlyphs::Ascii, _) => Some('\\'),
+            (LineGlyphs::BoxDrawing, 0) => Some('─'),
+            (LineGlyphs::BoxDrawing, 1) => Some('╱'),
+            (LineGlyphs::BoxDrawing, 2) => Some('│'),
+            (LineGlyphs::BoxDrawing, _) => Some('╲'),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_follow_slope() {
+        let ascii = |direction| LineGlyphs::Ascii.glyph(direction, false).unwrap();
+
+        assert_eq!(ascii((5.0, 1.0)), '-');
+        assert_eq!(ascii((-5.0, -1.0)), '-');
+        assert_eq!(LineGlyphs::Ascii.glyph((5.0, 1.0), true), Some('_'));
+        assert_eq!(ascii((1.0, 5.0)), '|');
+        assert_eq!(ascii((3.0, -3.0)), '/');
+        assert_eq!(ascii((-3.0, 3.0)), '/');
+        assert_eq!(ascii((3.0, 3.0)), '\\');
+        assert_eq!(LineGlyphs::BoxDrawing.glyph((0.0, 2.0), true), Some('│'));
+        assert_eq!(LineGlyphs::Fixed.glyph((1.0, 0.0), false), None);
+    }
+}
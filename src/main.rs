@@ -1,26 +1,158 @@
 pub mod camera;
+pub mod clip;
+pub mod feature_edges;
+pub mod framebuffer;
+pub mod lighting;
+pub mod line_glyph;
 pub mod matrix3;
 pub mod render;
+pub mod render_mode;
 pub mod shapes;
+pub mod subcell;
+pub mod target;
 pub mod terminal;
+pub mod texture;
 pub mod vector3;
 pub mod world;
 pub mod world_object;
 
 use std::{
+    cell::RefCell,
+    env,
+    fs::File,
+    io::BufWriter,
     ops::{Deref, DerefMut},
+    process,
+    rc::Rc,
     thread,
     time::{self, Duration},
 };
 
-use crate::{terminal::Terminal, vector3::Vector3, world::World};
+use crate::{
+    framebuffer::Stipple,
+    render_mode::RenderMode,
+    subcell::SubCellMode,
+    target::{AsciicastTarget, DumpFormat, DumpTarget, GifTarget},
+    terminal::{ColorSupport, Terminal},
+    texture::Texture,
+    vector3::Vector3,
+    world::World,
+};
 
 static FPS: u16 = 12;
 
+const USAGE: &str = "Usage: terminal-3d [--cast <file>] [--gif <file>] [--text <file>] [--html <file>] [--frame <n>] [--texture <file>] [--mode <mode>] [--dashed] [--subcell <mode>]";
+
+struct Options {
+    /// Path to record an asciicast to
+    cast_path: Option<String>,
+    /// Path to export an animated GIF to
+    gif_path: Option<String>,
+    /// Path to dump a frame to as plain text
+    text_path: Option<String>,
+    /// Path to dump a frame to as HTML
+    html_path: Option<String>,
+    /// Frame to dump, defaults to the last one
+    dump_frame: Option<u64>,
+    /// Path to a text file to draw on the sides of the cube
+    texture_path: Option<String>,
+    /// Which parts of objects are drawn
+    render_mode: RenderMode,
+    /// Whether to draw edges hidden behind faces as dashed lines
+    dashed: bool,
+    /// How pixels are packed into cells
+    subcell_mode: SubCellMode,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        cast_path: None,
+        gif_path: None,
+        text_path: None,
+        html_path: None,
+        dump_frame: None,
+        texture_path: None,
+        render_mode: RenderMode::default(),
+        dashed: false,
+        subcell_mode: SubCellMode::Character,
+    };
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cast" => {
+                options.cast_path = Some(args.next().ok_or("--cast requires a file path")?);
+            }
+            "--gif" => {
+                options.gif_path = Some(args.next().ok_or("--gif requires a file path")?);
+            }
+            "--text" => {
+                options.text_path = Some(args.next().ok_or("--text requires a file path")?);
+            }
+            "--html" => {
+                options.html_path = Some(args.next().ok_or("--html requires a file path")?);
+            }
+            "--frame" => {
+                let frame = args.next().ok_or("--frame requires a frame number")?;
+
+                options.dump_frame = Some(
+                    frame
+                        .parse()
+                        .map_err(|_| format!("Invalid frame number {}", frame))?,
+                );
+            }
+            "--texture" => {
+                options.texture_path = Some(args.next().ok_or("--texture requires a file path")?);
+            }
+            "--mode" => {
+                options.render_mode = args
+                    .next()
+                    .ok_or("--mode requires a render mode")?
+                    .parse()?;
+            }
+            "--dashed" => options.dashed = true,
+            "--subcell" => {
+                options.subcell_mode = args
+                    .next()
+                    .ok_or("--subcell requires a sub-cell mode")?
+                    .parse()?;
+            }
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn create_file(path: &str) -> BufWriter<File> {
+    let file = File::create(path).unwrap_or_else(|err| {
+        eprintln!("Could not create {}: {}", path, err);
+        process::exit(1);
+    });
+
+    BufWriter::new(file)
+}
+
 fn main() {
+    let options = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(2);
+    });
     let mut world = World::new();
 
-    world.add_world_object(Box::new(shapes::RotatingCube::default()));
+    let mut cube = shapes::RotatingCube::default();
+
+    if let Some(path) = &options.texture_path {
+        let texture = Texture::load(path, terminal::Color::Reset, terminal::Decor::None)
+            .unwrap_or_else(|err| {
+                eprintln!("Could not read {}: {}", path, err);
+                process::exit(1);
+            });
+
+        cube.set_texture(Some(texture));
+    }
+
+    world.add_world_object(Box::new(cube));
     world.add_world_object(Box::new(shapes::Point::new(
         vector3!(0, 0, -5),
         terminal::Color::Blue,
@@ -29,25 +161,78 @@ fn main() {
         vector3!(20, 20, 0),
         terminal::Color::Red,
     )));
+    world.add_world_object(Box::new(shapes::Plane::new(
+        vector3!(0, -8, 0),
+        vector3!(0, 1, 0),
+        40.0,
+    )));
+    world.add_light(Box::new(
+        lighting::DirectionalLight::new(vector3!(-1, -2, -1), terminal::Color::Reset, 0.9)
+            .with_shadows(128),
+    ));
+    world.add_light(Box::new(lighting::AmbientLight::new(
+        terminal::Color::Reset,
+        0.1,
+    )));
 
     let mut terminal = Terminal::new();
-    let camera = camera::PerspectiveCamera::new(
+    terminal.set_subcell_mode(options.subcell_mode);
+    terminal.set_render_mode(options.render_mode);
+    if options.dashed {
+        terminal.set_hidden_edges(Stipple::dashed(2, 2));
+    }
+    let camera = Rc::new(RefCell::new(camera::PerspectiveCamera::new(
         90,
         vector3!(0, 30, 30),
         vector3!(0, -1, -1),
         vector3!(0, 1, -1),
         terminal.get_term_size(),
-    );
+    )));
+    terminal.register_camera(camera.clone());
     let frame_time = Duration::from_secs_f64(1.0 / FPS as f64);
 
+    if let Some(path) = options.cast_path {
+        let mut recorder = AsciicastTarget::new(create_file(&path), ColorSupport::TrueColor);
+
+        recorder.set_frame_time(Some(frame_time));
+        terminal.add_sink(Box::new(recorder));
+    }
+    if let Some(path) = options.gif_path {
+        terminal.add_sink(Box::new(GifTarget::new(create_file(&path), FPS)));
+    }
+    if let Some(path) = options.text_path {
+        terminal.add_sink(Box::new(DumpTarget::new(
+            create_file(&path),
+            DumpFormat::Text,
+            options.dump_frame,
+        )));
+    }
+    if let Some(path) = options.html_path {
+        terminal.add_sink(Box::new(DumpTarget::new(
+            create_file(&path),
+            DumpFormat::Html,
+            options.dump_frame,
+        )));
+    }
+
     for frame in 0..200 {
         let start = time::Instant::now();
         for obj in world.values_mut() {
             obj.deref_mut().update(frame);
         }
+        for light in world.get_lights_mut() {
+            light.update(frame);
+        }
+        world.update_shadows();
         terminal.pre_render();
         for (id, obj) in world.iter() {
-            terminal.buffer_world_object(*id, obj.deref(), &camera, frame);
+            terminal.buffer_world_object(
+                *id,
+                obj.deref(),
+                &*camera.borrow(),
+                world.get_lights(),
+                frame,
+            );
         }
         let end = time::Instant::now();
         if end - start < frame_time {
@@ -159,7 +159,7 @@ impl Matrix3 {
     /// Orthonormalize using Gram-Schmidt, normalizing each column as we go
     /// Assumes columns of matrix are linearly independent
     pub fn orthonormalize(&self) -> Matrix3 {
-        let mut mat = self.clone();
+        let mut mat = *self;
 
         mat.set_col(0, mat.col(0).normalize());
 
@@ -180,14 +180,18 @@ impl Matrix3 {
     /// Get the minor of the i-th row and j-th column
     /// Instead of deleting the i-th row and j-th column, we can just set 1s and 0s to make it look like a 2x2 matrix
     pub fn minor(&self, i: usize, j: usize) -> f64 {
-        let mut mat = self.clone();
+        let mut mat = *self;
 
         for row in 0..=2 {
             mat.mat[row][j] = 0.0
         }
         for col in 0..=2 {
             if col == j {
-                mat.mat[i][col] = if (i + col) % 2 == 0 { 1.0 } else { -1.0 }
+                mat.mat[i][col] = if (i + col).is_multiple_of(2) {
+                    1.0
+                } else {
+                    -1.0
+                }
             } else {
                 mat.mat[i][col] = 0.0
             }
@@ -198,7 +202,7 @@ impl Matrix3 {
 
     /// Get the cofactor of the i-th row and j-th column
     pub fn cofactor(&self, i: usize, j: usize) -> f64 {
-        let is_even = (i + j) % 2 == 0;
+        let is_even = (i + j).is_multiple_of(2);
 
         if is_even {
             self.minor(i, j)
@@ -227,6 +231,7 @@ impl Matrix3 {
         }
     }
 
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         format!(
             "[ {:?}, {:?}, {:?} ]",
@@ -376,27 +381,27 @@ impl fmt::Display for Matrix3 {
         let mut strings: [[String; 3]; 3] = Default::default();
         let mut max_len: [usize; 3] = Default::default(); // Max length per column
 
-        for row in 0..=2 {
-            for col in 0..=2 {
-                strings[row][col] = format!("{}", self.mat[row][col]);
+        for (row, row_strings) in strings.iter_mut().enumerate() {
+            for (col, string) in row_strings.iter_mut().enumerate() {
+                *string = format!("{}", self.mat[row][col]);
 
-                if strings[row][col].len() > max_len[col] {
-                    max_len[col] = strings[row][col].len()
+                if string.len() > max_len[col] {
+                    max_len[col] = string.len()
                 }
             }
         }
 
         let mut rows: [String; 3] = Default::default();
 
-        for row in 0..=2 {
-            rows[row] = format!(
+        for (row, row_strings) in rows.iter_mut().zip(&strings) {
+            *row = format!(
                 "{}{} {}{} {}{}",
-                strings[row][0],
-                " ".repeat(max_len[0] - strings[row][0].len()),
-                strings[row][1],
-                " ".repeat(max_len[1] - strings[row][1].len()),
-                strings[row][2],
-                " ".repeat(max_len[2] - strings[row][2].len()),
+                row_strings[0],
+                " ".repeat(max_len[0] - row_strings[0].len()),
+                row_strings[1],
+                " ".repeat(max_len[1] - row_strings[1].len()),
+                row_strings[2],
+                " ".repeat(max_len[2] - row_strings[2].len()),
             )
         }
 
@@ -415,6 +420,7 @@ impl fmt::Display for Matrix3 {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use std::f64::consts::PI;
 
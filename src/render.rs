@@ -1,18 +1,65 @@
 use std::mem::swap;
 
-use crate::vector3;
 use crate::vector3::Vector3;
 
+/// Clip a line to the rectangle from (0, 0) to `viewport` (width, height) with the Liang-Barsky
+/// algorithm, extended by half a pixel on each side so pixels on the border are kept. The depth is
+/// interpolated along with the position. Returns None if the line is entirely outside.
+fn clip_line_to_viewport(
+    start: Vector3,
+    end: Vector3,
+    viewport: (u16, u16),
+) -> Option<(Vector3, Vector3)> {
+    let delta = end - start;
+    let (min_x, min_y) = (-0.5, -0.5);
+    let (max_x, max_y) = (viewport.0 as f64 - 0.5, viewport.1 as f64 - 0.5);
+    let (mut t0, mut t1) = (0.0, 1.0);
+
+    // Each boundary as (p, q), where the line is inside where t * p <= q
+    let boundaries = [
+        (-delta.x, start.x - min_x),
+        (delta.x, max_x - start.x),
+        (-delta.y, start.y - min_y),
+        (delta.y, max_y - start.y),
+    ];
+
+    for (p, q) in boundaries {
+        if p == 0.0 {
+            // Parallel to the boundary, so either entirely inside or outside it
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+
+            if p < 0.0 {
+                t0 = f64::max(t0, t);
+            } else {
+                t1 = f64::min(t1, t);
+            }
+        }
+    }
+
+    if t0 > t1 {
+        return None;
+    }
+
+    Some((start + delta * t0, start + delta * t1))
+}
+
 /// Implementation of Bresenhan's line rasterization algorithm, which also reports the depth of
 /// each point. All vectors have x and y components relative to the camera screen, and the z
-/// component represents the distance from the screen
+/// component represents the distance from the screen. The line is clipped to `viewport` (width,
+/// height) first, so only points on or next to the screen are generated.
 pub fn bresenham_line_3d(
-    _start: Vector3,
-    _end: Vector3,
+    start: Vector3,
+    end: Vector3,
+    viewport: (u16, u16),
     mut generate: impl FnMut((i64, i64), f64),
 ) {
-    let mut start: Vector3 = _start;
-    let mut end: Vector3 = _end;
+    let Some((mut start, mut end)) = clip_line_to_viewport(start, end, viewport) else {
+        return;
+    };
 
     let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
     if steep {
@@ -55,91 +102,327 @@ pub fn bresenham_line_3d(
     }
 }
 
-type VertexTriple = (Vector3, Vector3, Vector3);
-
-/// Sort points by y, ascending
-fn sort_by_y(vertices: VertexTriple) -> VertexTriple {
-    let mut result = vertices.clone();
+/// Implementation of Xiaolin Wu's anti-aliased line algorithm, which also reports the depth of
+/// each point. Each point is generated with its coverage, the share of the point covered by the
+/// line, from 0 to 1. Points come in pairs across the line, with coverage adding up to 1. The line
+/// is clipped to `viewport` (width, height) first, like in `bresenham_line_3d`.
+pub fn wu_line_3d(
+    start: Vector3,
+    end: Vector3,
+    viewport: (u16, u16),
+    mut generate: impl FnMut((i64, i64), f64, f64),
+) {
+    let Some((mut start, mut end)) = clip_line_to_viewport(start, end, viewport) else {
+        return;
+    };
 
-    if result.0.y > result.1.y {
-        swap(&mut result.0, &mut result.1);
+    let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
+    if steep {
+        (start.x, start.y) = (start.y, start.x);
+        (end.x, end.y) = (end.y, end.x);
     }
-    if result.0.y > result.2.y {
-        swap(&mut result.0, &mut result.2);
+    if end.x < start.x {
+        (start, end) = (end, start)
     }
-    if result.1.y > result.2.y {
-        swap(&mut result.1, &mut result.2);
+
+    let dx = end.x - start.x;
+    let gradient = if dx.abs() < f64::EPSILON {
+        0.0
+    } else {
+        (end.y - start.y) / dx
+    };
+    let depth_gradient = if dx.abs() < f64::EPSILON {
+        0.0
+    } else {
+        (end.z - start.z) / dx
+    };
+    let mut plot = |x: i64, y: f64, coverage: f64| {
+        let depth = start.z + (x as f64 - start.x) * depth_gradient;
+        let (y0, fraction) = (y.floor() as i64, y - y.floor());
+
+        for (y, coverage) in [
+            (y0, (1.0 - fraction) * coverage),
+            (y0 + 1, fraction * coverage),
+        ] {
+            if steep {
+                generate((y, x), depth, coverage);
+            } else {
+                generate((x, y), depth, coverage);
+            }
+        }
+    };
+
+    let (x0, x1) = (start.x.round() as i64, end.x.round() as i64);
+
+    if x0 == x1 {
+        // The whole line is within one column
+        plot(x0, (start.y + end.y) / 2.0, end.x - start.x);
+        return;
     }
 
-    result
+    // The ends only cover the part of their column the line reaches into
+    let start_gap = 1.0 - (start.x + 0.5 - (start.x + 0.5).floor());
+    let end_gap = end.x + 0.5 - (end.x + 0.5).floor();
+
+    plot(x0, start.y + gradient * (x0 as f64 - start.x), start_gap);
+    for x in x0 + 1..x1 {
+        plot(x, start.y + gradient * (x as f64 - start.x), 1.0);
+    }
+    plot(x1, start.y + gradient * (x1 as f64 - start.x), end_gap);
 }
 
-/// Sort points by y, ascending
-fn sort_by_x(vertices: VertexTriple) -> VertexTriple {
-    let mut result = vertices.clone();
+type VertexTriple = (Vector3, Vector3, Vector3);
 
-    if result.0.x > result.1.x {
-        swap(&mut result.0, &mut result.1);
-    }
-    if result.0.x > result.2.x {
-        swap(&mut result.0, &mut result.2);
+/// Correct barycentric `weights` found in screen space, so values interpolated with them are
+/// spread evenly across the triangle in the world. `depths` are the depths of the vertices in
+/// front of a perspective camera.
+pub fn perspective_correct(weights: (f64, f64, f64), depths: (f64, f64, f64)) -> (f64, f64, f64) {
+    let corrected = (
+        weights.0 / depths.0,
+        weights.1 / depths.1,
+        weights.2 / depths.2,
+    );
+    let sum = corrected.0 + corrected.1 + corrected.2;
+
+    if sum.abs() < f64::EPSILON {
+        return weights;
     }
-    if result.1.x > result.2.x {
-        swap(&mut result.1, &mut result.2);
+
+    (corrected.0 / sum, corrected.1 / sum, corrected.2 / sum)
+}
+
+/// Largest change in depth between neighbouring pixels of a triangle, along either screen axis
+pub fn depth_slope((a, b, c): VertexTriple) -> f64 {
+    let normal = (b - a) * (c - a);
+
+    if normal.z.abs() < f64::EPSILON {
+        return 0.0;
     }
 
-    result
+    f64::max((normal.x / normal.z).abs(), (normal.y / normal.z).abs())
+}
+
+/// Bits of sub-pixel precision that the vertices of triangles are snapped to
+const SUBPIXEL_BITS: u32 = 8;
+
+/// One pixel in fixed point
+const SUBPIXEL_ONE: i128 = 1 << SUBPIXEL_BITS;
+
+type FixedPoint = (i128, i128);
+
+fn to_fixed_point(vec: Vector3) -> FixedPoint {
+    let scale = SUBPIXEL_ONE as f64;
+
+    (
+        (vec.x * scale).round() as i128,
+        (vec.y * scale).round() as i128,
+    )
+}
+
+/// Twice the signed area of the triangle `from`, `to`, `point`. Positive if `point` is on the
+/// inside of the edge from `from` to `to`, for triangles wound the way
+/// `edge_function_triangle_3d` rasterizes them.
+fn edge_function(from: FixedPoint, to: FixedPoint, point: FixedPoint) -> i128 {
+    (to.0 - from.0) * (point.1 - from.1) - (to.1 - from.1) * (point.0 - from.0)
 }
 
-/// Get signed area of a triangle defined by 3 points using the shoelace formula
-fn get_triangle_area((a, b, c): VertexTriple) -> f64 {
-    ((b.y - a.y) * (b.x + a.x) + (c.y - b.y) * (c.x + b.x) + (a.y - c.y) * (a.x + c.x)) / 2.0
+/// Whether the edge from `from` to `to` is a top edge (horizontal, with the triangle below it) or
+/// a left edge
+fn is_top_left(from: FixedPoint, to: FixedPoint) -> bool {
+    let delta = (to.0 - from.0, to.1 - from.1);
+
+    delta.1 < 0 || (delta.1 == 0 && delta.0 > 0)
 }
 
-/// Implementation of a bounding-box-style triangle face renderer, which finds a bounding rectangle
-/// around the 3 points, and then tests every point in the box to see if it is in the triangle.
+/// Implementation of an edge function triangle face renderer. The edge functions of the 3 edges
+/// tell which side of each edge a point is on, and are stepped from pixel to pixel across a
+/// bounding rectangle around the 3 points.
 /// All vectors have x and y components relative to the camera screen, and the z component
-/// represents the distance from the screen.
+/// represents the distance from the screen. Vertices are snapped to 1/256th of a pixel, so the
+/// edge functions are exact.
+/// Pixels on an edge are only generated if it is a top or left edge, so triangles sharing an edge
+/// generate each pixel along it exactly once.
+/// The bounding box is clamped to `viewport` (width, height), so only points on the screen are
+/// generated.
+/// Each point is generated with its depth and its barycentric weights, the share each vertex has
+/// in the point, which can be used to interpolate values across the triangle.
 /// Note that the algorithm will `generate` points regardless of which way the triangle is "facing"
 /// (i.e no back-face culling is done). If a triangle is not facing the camera, this function
 /// should not be invoked at all.
-pub fn bounding_box_triangle_3d(vertices: VertexTriple, mut generate: impl FnMut((i64, i64), f64)) {
-    let triangle_area = get_triangle_area(vertices);
+pub fn edge_function_triangle_3d(
+    vertices: VertexTriple,
+    viewport: (u16, u16),
+    mut generate: impl FnMut((i64, i64), f64, (f64, f64, f64)),
+) {
+    let (a, mut b, mut c) = (
+        to_fixed_point(vertices.0),
+        to_fixed_point(vertices.1),
+        to_fixed_point(vertices.2),
+    );
+    let mut area = edge_function(a, b, c);
+
+    // Wind every triangle the same way, so the same edges count as top and left edges
+    let is_flipped = area < 0;
+    if is_flipped {
+        swap(&mut b, &mut c);
+        area = -area;
+    }
 
     // Degenerate triangle
-    if triangle_area.abs() < f64::EPSILON {
+    if area == 0 {
         return;
     }
 
-    let vertices_sorted_y = sort_by_y(vertices);
-    let vertices_sorted_x = sort_by_x(vertices);
+    // Pixels whose centers, at whole coordinates, are within the bounding box
+    let pixel_range = |min: i128, max: i128, size: u16| {
+        (
+            (min + SUBPIXEL_ONE - 1).div_euclid(SUBPIXEL_ONE).max(0),
+            max.div_euclid(SUBPIXEL_ONE).min(size as i128 - 1),
+        )
+    };
+    let x_range = pixel_range(a.0.min(b.0).min(c.0), a.0.max(b.0).max(c.0), viewport.0);
+    let y_range = pixel_range(a.1.min(b.1).min(c.1), a.1.max(b.1).max(c.1), viewport.1);
 
-    let y_range = (
-        vertices_sorted_y.0.y.round() as i64,
-        vertices_sorted_y.2.y.round() as i64,
-    );
-    let x_range = (
-        vertices_sorted_x.0.x.round() as i64,
-        vertices_sorted_x.2.x.round() as i64,
-    );
+    // Each edge is opposite the vertex whose weight its edge function gives
+    let edges = [(b, c), (c, a), (a, b)];
+    let start = (x_range.0 * SUBPIXEL_ONE, y_range.0 * SUBPIXEL_ONE);
+    let mut row = edges.map(|(from, to)| edge_function(from, to, start));
+    let step_x = edges.map(|(from, to)| -(to.1 - from.1) * SUBPIXEL_ONE);
+    let step_y = edges.map(|(from, to)| (to.0 - from.0) * SUBPIXEL_ONE);
+    // Points exactly on an edge are only inside if it is a top or left edge
+    let bias = edges.map(|(from, to)| if is_top_left(from, to) { 0 } else { -1 });
 
-    // Loop through each coordinate in the bounding box
     for y in y_range.0..=y_range.1 {
-        for x in x_range.0..=x_range.1 {
-            let point = vector3!(x, y, 0);
+        let mut values = row;
 
-            // Determine if (x, y) is inside the triangle defined by vertices
-            let alpha = get_triangle_area((point, vertices.1, vertices.2)) / triangle_area;
-            let beta = get_triangle_area((point, vertices.2, vertices.0)) / triangle_area;
-            let gamma = get_triangle_area((point, vertices.0, vertices.1)) / triangle_area;
+        for x in x_range.0..=x_range.1 {
+            if (0..3).all(|i| values[i] + bias[i] >= 0) {
+                let weight = |value: i128| value as f64 / area as f64;
+                let weights = if is_flipped {
+                    (weight(values[0]), weight(values[2]), weight(values[1]))
+                } else {
+                    (weight(values[0]), weight(values[1]), weight(values[2]))
+                };
+                let depth =
+                    weights.0 * vertices.0.z + weights.1 * vertices.1.z + weights.2 * vertices.2.z;
 
-            if alpha < 0.0 || beta < 0.0 || gamma < 0.0 {
-                continue;
+                generate((x as i64, y as i64), depth, weights);
             }
 
-            let depth = alpha * vertices.0.z + beta * vertices.1.z + gamma * vertices.2.z;
+            for i in 0..3 {
+                values[i] += step_x[i];
+            }
+        }
 
-            generate((x, y), depth);
+        for i in 0..3 {
+            row[i] += step_y[i];
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector3;
+
+    #[test]
+    fn clip_to_viewport() {
+        let mut pixels = vec![];
+
+        // Only the part of the line crossing the 10x10 viewport is walked, plus at most a pixel
+        // on either side from rounding
+        bresenham_line_3d(
+            vector3!(-1e9, 5, 0),
+            vector3!(1e9, 5, 2),
+            (10, 10),
+            |pixel, _| pixels.push(pixel),
+        );
+        assert!((0..10).all(|x| pixels.contains(&(x, 5))));
+        assert!(
+            pixels
+                .iter()
+                .all(|&(x, y)| (-1..=10).contains(&x) && y == 5)
+        );
+
+        pixels.clear();
+        bresenham_line_3d(
+            vector3!(-5, -5, 0),
+            vector3!(20, -1, 0),
+            (10, 10),
+            |pixel, _| pixels.push(pixel),
+        );
+        assert!(pixels.is_empty());
+
+        let mut count = 0;
+
+        edge_function_triangle_3d(
+            (
+                vector3!(-1e6, -1e6, 1),
+                vector3!(1e6, -1e6, 1),
+                vector3!(0, 1e6, 1),
+            ),
+            (10, 10),
+            |_, _, _| count += 1,
+        );
+        assert_eq!(count, 100);
+    }
+
+    #[test]
+    fn wu_line_coverage() {
+        let mut coverages = vec![];
+
+        wu_line_3d(
+            vector3!(0, 2.5, 0),
+            vector3!(3, 2.5, 0),
+            (10, 10),
+            |pixel, _, coverage| coverages.push((pixel, coverage)),
+        );
+        // A line halfway between two rows covers both evenly, and only half of the end columns
+        assert_eq!(
+            coverages,
+            vec![
+                ((0, 2), 0.25),
+                ((0, 3), 0.25),
+                ((1, 2), 0.5),
+                ((1, 3), 0.5),
+                ((2, 2), 0.5),
+                ((2, 3), 0.5),
+                ((3, 2), 0.25),
+                ((3, 3), 0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_rule() {
+        let mut pixels = vec![];
+        let (a, b, c, d) = (
+            vector3!(0, 0, 0),
+            vector3!(4, 0, 0),
+            vector3!(4, 4, 0),
+            vector3!(0, 4, 0),
+        );
+
+        // Two triangles sharing a diagonal generate each pixel of the square once, leaving out
+        // its right and bottom edges, whichever way they are wound
+        edge_function_triangle_3d((a, b, c), (10, 10), |pixel, _, _| pixels.push(pixel));
+        edge_function_triangle_3d((a, d, c), (10, 10), |pixel, _, _| pixels.push(pixel));
+        pixels.sort();
+        assert_eq!(
+            pixels,
+            (0..4)
+                .flat_map(|x| (0..4).map(move |y| (x, y)))
+                .collect::<Vec<_>>()
+        );
+
+        // Weights are given in the order of the vertices
+        let mut found = None;
+
+        edge_function_triangle_3d((c, d, a), (10, 10), |pixel, depth, weights| {
+            if pixel == (0, 3) {
+                found = Some((depth, weights));
+            }
+        });
+        assert_eq!(found, Some((0.0, (0.0, 0.75, 0.25))));
+    }
+}
@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+/// Which parts of objects are drawn
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum RenderMode {
+    /// Only vertices
+    Points,
+    /// Vertices and edges, seen through faces
+    Wireframe,
+    /// Only faces
+    Solid,
+    /// Vertices, edges and faces
+    #[default]
+    SolidWireframe,
+    /// Vertices and edges, where faces hide the parts behind them without being drawn themselves
+    HiddenLine,
+}
+
+impl RenderMode {
+    pub fn draws_vertices(self) -> bool {
+        self != RenderMode::Solid
+    }
+
+    pub fn draws_edges(self) -> bool {
+        !matches!(self, RenderMode::Points | RenderMode::Solid)
+    }
+
+    pub fn draws_faces(self) -> bool {
+        matches!(self, RenderMode::Solid | RenderMode::SolidWireframe)
+    }
+
+    /// Whether faces are rasterized only to hide what is behind them
+    pub fn hides_with_faces(self) -> bool {
+        self == RenderMode::HiddenLine
+    }
+}
+
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<RenderMode, String> {
+        match name {
+            "points" => Ok(RenderMode::Points),
+            "wireframe" => Ok(RenderMode::Wireframe),
+            "solid" => Ok(RenderMode::Solid),
+            "solid-wireframe" => Ok(RenderMode::SolidWireframe),
+            "hidden-line" => Ok(RenderMode::HiddenLine),
+            _ => Err(format!("Unknown render mode {}", name)),
+        }
+    }
+}
@@ -1,7 +1,10 @@
+pub mod plane;
 pub mod point;
 pub mod rotating_cube;
 pub mod rotating_square;
+pub mod sphere;
 
 pub use crate::shapes::{
-    point::Point, rotating_cube::RotatingCube, rotating_square::RotatingSquare,
+    plane::Plane, point::Point, rotating_cube::RotatingCube, rotating_square::RotatingSquare,
+    sphere::Sphere,
 };
@@ -0,0 +1,44 @@
+use crate::{vector3, vector3::Vector3, world_object::WorldObject};
+
+/// Flat square, facing the side its normal points to. Useful as a floor for other objects to cast
+/// shadows on.
+pub struct Plane {
+    vertices: Vec<Vector3>,
+}
+
+impl Plane {
+    pub fn new(middle: Vector3, normal: Vector3, size: f64) -> Plane {
+        let normal = normal.normalize();
+        let other = if normal.x.abs() < 0.9 {
+            vector3!(1, 0, 0)
+        } else {
+            vector3!(0, 1, 0)
+        };
+        // Two directions along the plane, so that u x v = normal
+        let u = (other - other.project(normal)).normalize() * (size / 2.0);
+        let v = normal * u;
+
+        Plane {
+            vertices: vec![
+                middle - u - v,
+                middle + u - v,
+                middle + u + v,
+                middle - u + v,
+            ],
+        }
+    }
+}
+
+impl WorldObject for Plane {
+    fn vectices(&self) -> Vec<Vector3> {
+        self.vertices.clone()
+    }
+
+    fn edges(&self) -> Vec<(usize, usize)> {
+        vec![(0, 1), (1, 2), (2, 3), (3, 0)]
+    }
+
+    fn triangles(&self) -> Vec<(usize, usize, usize)> {
+        vec![(0, 1, 2), (0, 2, 3)]
+    }
+}
@@ -1,13 +1,21 @@
 use std::f64::consts::PI;
 
+use crate::{
+    line_glyph::LineGlyphs,
+    matrix3::Matrix3,
+    terminal,
+    texture::{Texture, Uv},
+    vector3::Vector3,
+    world_object::WorldObject,
+};
 use crate::{matrix3, vector3};
-use crate::{matrix3::Matrix3, terminal, vector3::Vector3, world_object::WorldObject};
 
 pub struct RotatingCube {
     vertices: Vec<Vector3>,
     edges: Vec<(usize, usize)>,
     faces: Vec<(usize, usize, usize)>,
     rotation_point: Vector3,
+    texture: Option<Texture>,
 }
 
 impl RotatingCube {
@@ -54,10 +62,18 @@ impl RotatingCube {
                 (0, 5, 4),
             ],
             rotation_point: middle,
+            texture: None,
         }
     }
 
-    pub fn default() -> RotatingCube {
+    /// Draw `texture` on every side of the cube
+    pub fn set_texture(&mut self, texture: Option<Texture>) {
+        self.texture = texture;
+    }
+}
+
+impl Default for RotatingCube {
+    fn default() -> RotatingCube {
         RotatingCube::new(Vector3::zero(), 10)
     }
 }
@@ -75,6 +91,10 @@ impl WorldObject for RotatingCube {
         )
     }
 
+    fn line_glyphs(&self) -> LineGlyphs {
+        LineGlyphs::Ascii
+    }
+
     fn edges(&self) -> Vec<(usize, usize)> {
         // TODO: can we do this without a deep copy?
         self.edges.clone()
@@ -84,6 +104,21 @@ impl WorldObject for RotatingCube {
         self.faces.clone()
     }
 
+    fn texture(&self) -> Option<&Texture> {
+        self.texture.as_ref()
+    }
+
+    fn triangle_uvs(&self) -> Vec<(Uv, Uv, Uv)> {
+        // Each side is split into two triangles along the diagonal from its first corner
+        let sides = self.faces.len() / 2;
+
+        [
+            ((0.0, 1.0), (1.0, 1.0), (1.0, 0.0)),
+            ((0.0, 1.0), (1.0, 0.0), (0.0, 0.0)),
+        ]
+        .repeat(sides)
+    }
+
     fn update(&mut self, frame: u64) {
         if frame == 0 {
             return;
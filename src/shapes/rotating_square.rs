@@ -26,8 +26,10 @@ impl RotatingSquare {
             rotation_point: middle,
         }
     }
+}
 
-    pub fn default() -> RotatingSquare {
+impl Default for RotatingSquare {
+    fn default() -> RotatingSquare {
         RotatingSquare::new(Vector3::zero(), 10)
     }
 }
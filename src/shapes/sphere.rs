@@ -0,0 +1,118 @@
+use std::f64::consts::PI;
+
+use crate::{
+    feature_edges::FeatureEdges, lighting::ShadingMode, vector3, vector3::Vector3,
+    world_object::WorldObject,
+};
+
+/// UV sphere, smooth shaded with its exact normals
+pub struct Sphere {
+    center: Vector3,
+    vertices: Vec<Vector3>,
+    triangles: Vec<(usize, usize, usize)>,
+}
+
+impl Sphere {
+    /// Sphere made of `rings` bands of latitude, each split into `segments` faces around the axis
+    pub fn new(center: Vector3, radius: f64, rings: usize, segments: usize) -> Sphere {
+        let rings = rings.max(2);
+        let segments = segments.max(3);
+        let mut vertices = vec![center + vector3!(0, radius, 0)];
+        let mut triangles = vec![];
+
+        for ring in 1..rings {
+            let polar = PI * ring as f64 / rings as f64;
+
+            for segment in 0..segments {
+                let azimuth = 2.0 * PI * segment as f64 / segments as f64;
+
+                vertices.push(
+                    center
+                        + vector3!(
+                            polar.sin() * azimuth.sin(),
+                            polar.cos(),
+                            polar.sin() * azimuth.cos()
+                        ) * radius,
+                );
+            }
+        }
+        vertices.push(center + vector3!(0, -radius, 0));
+
+        let bottom = vertices.len() - 1;
+        let ring_start = |ring: usize| 1 + (ring - 1) * segments;
+
+        for segment in 0..segments {
+            let next = (segment + 1) % segments;
+
+            triangles.push((0, ring_start(1) + segment, ring_start(1) + next));
+
+            for ring in 1..rings - 1 {
+                let (upper, lower) = (ring_start(ring), ring_start(ring + 1));
+
+                triangles.push((upper + segment, lower + segment, lower + next));
+                triangles.push((upper + segment, lower + next, upper + next));
+            }
+
+            let last = ring_start(rings - 1);
+
+            triangles.push((last + segment, bottom, last + next));
+        }
+
+        Sphere {
+            center,
+            vertices,
+            triangles,
+        }
+    }
+}
+
+impl WorldObject for Sphere {
+    fn vectices(&self) -> Vec<Vector3> {
+        self.vertices.clone()
+    }
+
+    fn triangles(&self) -> Vec<(usize, usize, usize)> {
+        self.triangles.clone()
+    }
+
+    /// Outlined by its silhouette
+    fn feature_edges(&self) -> Option<FeatureEdges> {
+        Some(FeatureEdges {
+            crease_angle: None,
+            ..FeatureEdges::default()
+        })
+    }
+
+    fn shading_mode(&self) -> ShadingMode {
+        ShadingMode::Smooth
+    }
+
+    fn vertex_normals(&self) -> Vec<Vector3> {
+        self.vertices
+            .iter()
+            .map(|&vertex| (vertex - self.center).normalize())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lighting::face_normal;
+
+    #[test]
+    fn faces_point_outwards() {
+        let sphere = Sphere::new(vector3!(1, 2, 3), 5.0, 6, 8);
+        let vertices = sphere.vectices();
+
+        assert_eq!(vertices.len(), 2 + 5 * 8);
+        assert_eq!(sphere.triangles().len(), 2 * 8 + 2 * 4 * 8);
+
+        for (a, b, c) in sphere.triangles() {
+            let (a, b, c) = (vertices[a], vertices[b], vertices[c]);
+            let centroid = (a + b + c) / 3.0;
+
+            assert!(face_normal(a, b, c).dot(centroid - vector3!(1, 2, 3)) > 0.0);
+        }
+    }
+}
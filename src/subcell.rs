@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+/// Quadrant block characters, indexed by a mask of the pixels that are set
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Braille dot bits, indexed by pixel position within the cell (row * 2 + column)
+const BRAILLE_DOTS: [u8; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+/// Encoding used to pack multiple pixels into a single terminal cell. Pixels within a cell are
+/// numbered row by row, so the pixel in row `y` and column `x` of a cell is bit `y * width + x`
+/// of the mask passed to `glyph`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SubCellMode {
+    /// Each cell shows the character of the closest of its 2 vertically stacked pixels
+    Character,
+    /// `▀`/`▄` half blocks, where the top and bottom pixels get separate foreground and
+    /// background colors
+    HalfBlock,
+    /// 2x2 quadrant blocks
+    Quadrant,
+    /// 2x3 sextant blocks
+    Sextant,
+    /// 2x4 Braille dots
+    Braille,
+}
+
+impl SubCellMode {
+    /// Number of pixels in each cell, (width, height)
+    pub fn scale(self) -> (u16, u16) {
+        match self {
+            SubCellMode::Character => (1, 2),
+            SubCellMode::HalfBlock => (1, 2),
+            SubCellMode::Quadrant => (2, 2),
+            SubCellMode::Sextant => (2, 3),
+            SubCellMode::Braille => (2, 4),
+        }
+    }
+
+    /// Width of each pixel divided by its height, for cells twice as tall as they are wide
+    pub fn pixel_aspect(self) -> f64 {
+        let (width, height) = self.scale();
+
+        height as f64 / (2.0 * width as f64)
+    }
+
+    /// Character that draws the pixels set in `mask`. Not meaningful for `Character` mode, where
+    /// the pixel's own character is used instead.
+    pub fn glyph(self, mask: u8) -> char {
+        match self {
+            SubCellMode::Character => {
+                if mask == 0 {
+                    ' '
+                } else {
+                    '█'
+                }
+            }
+            SubCellMode::HalfBlock => {
+                QUADRANTS[match mask & 0b11 {
+                    0b01 => 0b0011,
+                    0b10 => 0b1100,
+                    0b11 => 0b1111,
+                    _ => 0,
+                }]
+            }
+            SubCellMode::Quadrant => QUADRANTS[(mask & 0b1111) as usize],
+            SubCellMode::Sextant => sextant(mask & 0b11_1111),
+            SubCellMode::Braille => {
+                let dots = (0..8)
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .fold(0, |dots, bit| dots | BRAILLE_DOTS[bit]);
+
+                char::from_u32(0x2800 + dots as u32).unwrap()
+            }
+        }
+    }
+
+    /// Pixels drawn by `character`, the reverse of `glyph`. None if the mode never produces
+    /// `character`.
+    pub fn mask(self, character: char) -> Option<u8> {
+        let (scale_x, scale_y) = self.scale();
+        let pixel_count = scale_x * scale_y;
+
+        (0..(1u16 << pixel_count))
+            .map(|mask| mask as u8)
+            .find(|&mask| self.glyph(mask) == character)
+    }
+}
+
+/// Sextant characters start at U+1FB00 and skip the patterns that already exist as half and full
+/// blocks
+fn sextant(mask: u8) -> char {
+    match mask {
+        0 => ' ',
+        0b01_0101 => '▌',
+        0b10_1010 => '▐',
+        0b11_1111 => '█',
+        _ => {
+            let skipped = (mask > 0b01_0101) as u32 + (mask > 0b10_1010) as u32;
+
+            char::from_u32(0x1FB00 + mask as u32 - 1 - skipped).unwrap()
+        }
+    }
+}
+
+impl FromStr for SubCellMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<SubCellMode, String> {
+        match name {
+            "character" => Ok(SubCellMode::Character),
+            "half" => Ok(SubCellMode::HalfBlock),
+            "quadrant" => Ok(SubCellMode::Quadrant),
+            "sextant" => Ok(SubCellMode::Sextant),
+            "braille" => Ok(SubCellMode::Braille),
+            _ => Err(format!("Unknown sub-cell mode {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        camera::{Camera, IsoCamera, PerspectiveCamera},
+        vector3,
+        vector3::Vector3,
+    };
+
+    #[test]
+    fn glyphs() {
+        assert_eq!(SubCellMode::HalfBlock.glyph(0b01), '▀');
+        assert_eq!(SubCellMode::HalfBlock.glyph(0b10), '▄');
+        assert_eq!(SubCellMode::Quadrant.glyph(0b1001), '▚');
+        assert_eq!(SubCellMode::Sextant.glyph(0b00_0001), '\u{1FB00}');
+        assert_eq!(SubCellMode::Sextant.glyph(0b01_0101), '▌');
+        assert_eq!(SubCellMode::Sextant.glyph(0b01_0110), '\u{1FB14}');
+        assert_eq!(SubCellMode::Sextant.glyph(0b11_1110), '\u{1FB3B}');
+        assert_eq!(SubCellMode::Braille.glyph(0b1100_0001), '⣁');
+        assert_eq!(SubCellMode::Braille.glyph(0xFF), '⣿');
+    }
+
+    #[test]
+    fn squares_stay_square() {
+        let modes = [
+            SubCellMode::Character,
+            SubCellMode::HalfBlock,
+            SubCellMode::Quadrant,
+            SubCellMode::Sextant,
+            SubCellMode::Braille,
+        ];
+
+        for mode in modes {
+            let (scale_x, scale_y) = mode.scale();
+            let screen_size = (40 * scale_x, 20 * scale_y);
+            let mut cameras: [Box<dyn Camera>; 2] = [
+                Box::new(IsoCamera::default(screen_size)),
+                Box::new(PerspectiveCamera::default(screen_size)),
+            ];
+
+            for camera in &mut cameras {
+                camera.update_pixel_aspect(mode.pixel_aspect());
+
+                let corner = camera.project_vector(vector3!(-4, -4, 0));
+                let opposite = camera.project_vector(vector3!(4, 4, 0));
+                // Size on the terminal, where cells are 1 wide and 2 tall
+                let width = (opposite.x - corner.x).abs() / scale_x as f64;
+                let height = (opposite.y - corner.y).abs() * 2.0 / scale_y as f64;
+
+                assert!((width - height).abs() < 1e-9, "{:?}", mode);
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt::Write;
+
+use crate::{
+    framebuffer::Framebuffer,
+    terminal::{Cell, Color, ColorSupport, Decor},
+};
+
+/// SGR parameters to set the foreground, or background if `is_background`, to `color`
+fn color_sgr(
+    color: Color,
+    is_high_intensity: bool,
+    is_background: bool,
+    support: ColorSupport,
+) -> String {
+    let layer = if is_background { 4 } else { 3 };
+    let basic_sgr = |num: u8, is_high_intensity: bool| {
+        if is_high_intensity {
+            format!("{}{}", layer + 6, num)
+        } else {
+            format!("{}{}", layer, num)
+        }
+    };
+
+    match color.downgrade(support) {
+        Color::Reset => format!("{}9", layer),
+        Color::Black => basic_sgr(0, is_high_intensity),
+        Color::Red => basic_sgr(1, is_high_intensity),
+        Color::Green => basic_sgr(2, is_high_intensity),
+        Color::Yellow => basic_sgr(3, is_high_intensity),
+        Color::Blue => basic_sgr(4, is_high_intensity),
+        Color::Purple => basic_sgr(5, is_high_intensity),
+        Color::Cyan => basic_sgr(6, is_high_intensity),
+        Color::White => basic_sgr(7, is_high_intensity),
+        Color::Indexed(index) if support == ColorSupport::Basic => {
+            basic_sgr(index % 8, is_high_intensity || index >= 8)
+        }
+        Color::Indexed(index) => format!("{}8;5;{}", layer, index),
+        Color::Rgb(r, g, b) => format!("{}8;2;{};{};{}", layer, r, g, b),
+    }
+}
+
+fn get_cell_escape(cell: Cell, support: ColorSupport) -> String {
+    let is_high_intensity =
+        cell.decor == Decor::HighIntensity || cell.decor == Decor::BoldHighIntensity;
+    let decor_num = match cell.decor {
+        Decor::None => 0,
+        Decor::Bold => 1,
+        Decor::Underline => 4,
+        Decor::HighIntensity => 0,
+        Decor::BoldHighIntensity => 1,
+    };
+    // Start by resetting, so nothing from the previous cell carries over
+    let mut escape = String::from("\x1b[0");
+
+    if cell.color == Color::Reset && cell.background == Color::Reset {
+        return escape + "m";
+    }
+    if decor_num != 0 {
+        write!(escape, ";{}", decor_num).unwrap();
+    }
+    if cell.color != Color::Reset {
+        write!(
+            escape,
+            ";{}",
+            color_sgr(cell.color, is_high_intensity, false, support)
+        )
+        .unwrap();
+    }
+    if cell.background != Color::Reset {
+        write!(
+            escape,
+            ";{}",
+            color_sgr(cell.background, false, true, support)
+        )
+        .unwrap();
+    }
+
+    escape + "m"
+}
+
+/// Converts frames to ANSI escape sequences. Keeps track of what has already been written, so only
+/// cells that changed since the last frame are written.
+pub struct AnsiEncoder {
+    color_support: ColorSupport,
+    /// Cells of the frame currently on screen, None where unknown
+    emitted: Vec<Option<Cell>>,
+    /// Width of the frame currently on screen
+    emitted_width: u16,
+    /// Colors and decor currently set on the terminal, None if unknown
+    current_sgr: Option<(Color, Color, Decor)>,
+    full_repaint_requested: bool,
+    /// Whether full repaints start by clearing the screen
+    clears_screen: bool,
+}
+
+impl AnsiEncoder {
+    pub fn new(color_support: ColorSupport) -> AnsiEncoder {
+        AnsiEncoder {
+            color_support,
+            emitted: vec![],
+            emitted_width: 0,
+            current_sgr: None,
+            full_repaint_requested: true,
+            clears_screen: true,
+        }
+    }
+
+    pub fn get_color_support(&self) -> ColorSupport {
+        self.color_support
+    }
+
+    pub fn set_color_support(&mut self, color_support: ColorSupport) {
+        self.color_support = color_support;
+        self.full_repaint_requested = true;
+    }
+
+    /// Set whether full repaints start by clearing the screen. Output that is captured to a file
+    /// rather than shown on a terminal should not clear the screen.
+    pub fn set_clears_screen(&mut self, clears_screen: bool) {
+        self.clears_screen = clears_screen;
+    }
+
+    /// Force the next call to `encode` to clear the screen and redraw every cell
+    pub fn request_full_repaint(&mut self) {
+        self.full_repaint_requested = true;
+    }
+
+    /// Build the output for `framebuffer`. Only cells that differ from the frame currently on
+    /// screen are written, unless a full repaint is needed.
+    pub fn encode(&mut self, framebuffer: &Framebuffer) -> String {
+        let (width, height) = framebuffer.get_size();
+        let cell_count = width as usize * height as usize;
+        let mut output = String::new();
+
+        if self.full_repaint_requested
+            || self.emitted.len() != cell_count
+            || self.emitted_width != width
+        {
+            // \x1b[0m: reset style
+            output.push_str("\x1b[0m");
+            if self.clears_screen {
+                // \x1b[2J: clear screen
+                output.push_str("\x1b[2J");
+            }
+            self.emitted = vec![None; cell_count];
+            self.emitted_width = width;
+            self.current_sgr = Some((Color::Reset, Color::Reset, Decor::None));
+            self.full_repaint_requested = false;
+        }
+
+        // Cursor position as (row, column), None if unknown
+        let mut cursor: Option<(usize, usize)> = None;
+
+        for i in 0..cell_count {
+            let (row, col) = (i / width as usize, i % width as usize);
+            let cell = framebuffer.get_cell(col, row);
+
+            if self.emitted[i] == Some(cell) {
+                continue;
+            }
+
+            match cursor {
+                Some(position) if position == (row, col) => {}
+                // \x1b[nC: move cursor n columns right
+                Some((cursor_row, cursor_col)) if cursor_row == row && cursor_col < col => {
+                    write!(output, "\x1b[{}C", col - cursor_col).unwrap();
+                }
+                // \x1b[row;colH: move cursor to row, col (1-indexed)
+                _ => write!(output, "\x1b[{};{}H", row + 1, col + 1).unwrap(),
+            }
+
+            let sgr = (cell.color, cell.background, cell.decor);
+
+            if self.current_sgr != Some(sgr) {
+                output.push_str(&get_cell_escape(cell, self.color_support));
+                self.current_sgr = Some(sgr);
+            }
+
+            output.push(cell.character);
+            self.emitted[i] = Some(cell);
+            // Writing to the last column leaves the cursor position up to the terminal
+            cursor = if col + 1 < width as usize {
+                Some((row, col + 1))
+            } else {
+                None
+            };
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        camera::PerspectiveCamera, shapes::RotatingCube, subcell::SubCellMode, target::point_frame,
+        vector3, vector3::Vector3,
+    };
+
+    #[test]
+    fn cell_escape() {
+        let cell = Cell {
+            character: 'x',
+            color: Color::Rgb(1, 2, 3),
+            background: Color::Indexed(200),
+            decor: Decor::Bold,
+        };
+
+        assert_eq!(
+            get_cell_escape(cell, ColorSupport::TrueColor),
+            "\x1b[0;1;38;2;1;2;3;48;5;200m"
+        );
+        assert_eq!(
+            get_cell_escape(
+                Cell::from(('x', Color::Blue, Decor::HighIntensity)),
+                ColorSupport::Basic
+            ),
+            "\x1b[0;94m"
+        );
+        assert_eq!(
+            get_cell_escape(
+                Cell::from(('x', Color::Indexed(9), Decor::None)),
+                ColorSupport::Basic
+            ),
+            "\x1b[0;91m"
+        );
+    }
+
+    #[test]
+    fn only_changes_are_encoded() {
+        let mut framebuffer = Framebuffer::new((40, 20), SubCellMode::Character);
+        let camera = PerspectiveCamera::default(framebuffer.get_screen_size());
+        let mut encoder = AnsiEncoder::new(ColorSupport::TrueColor);
+        let cube = RotatingCube::new(vector3!(0, 0, 0), 20);
+
+        framebuffer.buffer_world_object(0, &cube, &camera, &[], 1);
+
+        let first = encoder.encode(&framebuffer);
+
+        assert!(first.starts_with("\x1b[0m\x1b[2J"));
+        assert!(first.contains('X'));
+        assert_eq!(encoder.encode(&framebuffer), "");
+
+        encoder.request_full_repaint();
+        assert_eq!(encoder.encode(&framebuffer), first);
+    }
+
+    #[test]
+    fn changed_cell_is_encoded() {
+        let mut framebuffer = point_frame((4, 3), Color::Red);
+        let mut encoder = AnsiEncoder::new(ColorSupport::TrueColor);
+
+        encoder.encode(&framebuffer);
+
+        // Only the cell the point left is written, after moving the cursor to it. The style is
+        // already reset by the blank cells written after the point.
+        framebuffer.clear();
+        assert_eq!(encoder.encode(&framebuffer), "\x1b[2;3H ");
+
+        // A forced repaint clears the screen and writes every cell again
+        encoder.request_full_repaint();
+        assert_eq!(
+            encoder.encode(&framebuffer),
+            "\x1b[0m\x1b[2J\x1b[1;1H    \x1b[2;1H    \x1b[3;1H    "
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    framebuffer::Framebuffer,
+    target::{AnsiEncoder, RenderTarget},
+    terminal::ColorSupport,
+};
+
+/// Quote and escape a string for JSON
+fn json_string(string: &str) -> String {
+    let mut result = String::with_capacity(string.len() + 2);
+
+    result.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                write!(result, "\\u{:04x}", character as u32).unwrap();
+            }
+            character => result.push(character),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+/// Records frames as an asciicast v2 file (https://docs.asciinema.org/manual/asciicast/v2/), which
+/// can be played back with asciinema. The header is written with the first frame, using its size.
+pub struct AsciicastTarget<W: Write> {
+    writer: W,
+    encoder: AnsiEncoder,
+    /// Size in the header or the latest resize event, None until the header is written
+    size: Option<(u16, u16)>,
+    start: Instant,
+    /// Fixed time between frames. If None, frames are timestamped with the wall clock.
+    frame_time: Option<Duration>,
+    frame_count: u64,
+}
+
+impl<W: Write> AsciicastTarget<W> {
+    pub fn new(writer: W, color_support: ColorSupport) -> AsciicastTarget<W> {
+        AsciicastTarget {
+            writer,
+            encoder: AnsiEncoder::new(color_support),
+            size: None,
+            start: Instant::now(),
+            frame_time: None,
+            frame_count: 0,
+        }
+    }
+
+    /// Timestamp frames at fixed intervals instead of with the wall clock, so recordings of the
+    /// same scene are identical
+    pub fn set_frame_time(&mut self, frame_time: Option<Duration>) {
+        self.frame_time = frame_time;
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_header(&mut self, size: (u16, u16)) -> io::Result<()> {
+        let mut header = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}",
+            size.0, size.1
+        );
+
+        // A timestamp would make otherwise identical recordings differ
+        if self.frame_time.is_none() {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0);
+
+            write!(header, ", \"timestamp\": {}", timestamp).unwrap();
+        }
+
+        writeln!(self.writer, "{}}}", header)
+    }
+
+    fn write_event(&mut self, time: f64, code: &str, data: &str) -> io::Result<()> {
+        writeln!(
+            self.writer,
+            "[{:.6}, {}, {}]",
+            time,
+            json_string(code),
+            json_string(data)
+        )
+    }
+}
+
+impl<W: Write> RenderTarget for AsciicastTarget<W> {
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()> {
+        let size = framebuffer.get_size();
+
+        if self.size.is_none() {
+            self.start = Instant::now();
+        }
+
+        let time = match self.frame_time {
+            Some(frame_time) => frame_time.as_secs_f64() * self.frame_count as f64,
+            None => self.start.elapsed().as_secs_f64(),
+        };
+
+        match self.size {
+            None => self.write_header(size)?,
+            Some(prev_size) if prev_size != size => {
+                self.write_event(time, "r", &format!("{}x{}", size.0, size.1))?;
+            }
+            _ => {}
+        }
+        self.size = Some(size);
+
+        let output = self.encoder.encode(framebuffer);
+
+        if !output.is_empty() {
+            self.write_event(time, "o", &output)?;
+        }
+        self.frame_count += 1;
+
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subcell::SubCellMode;
+
+    #[test]
+    fn escape_json() {
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(json_string("\x1b[0m█"), "\"\\u001b[0m█\"");
+    }
+
+    #[test]
+    fn record_frames() {
+        let mut framebuffer = Framebuffer::new((4, 2), SubCellMode::Character);
+        let mut recorder = AsciicastTarget::new(vec![], ColorSupport::Basic);
+
+        recorder.set_frame_time(Some(Duration::from_millis(500)));
+        recorder.present(&framebuffer).unwrap();
+        recorder.present(&framebuffer).unwrap();
+        framebuffer.resize((3, 2));
+        recorder.present(&framebuffer).unwrap();
+
+        let output = String::from_utf8(recorder.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "{\"version\": 2, \"width\": 4, \"height\": 2}");
+        assert!(lines[1].starts_with("[0.000000, \"o\", \"\\u001b[0m\\u001b[2J"));
+        // Nothing changed in the second frame, so there is no output event
+        assert_eq!(lines[2], "[1.000000, \"r\", \"3x2\"]");
+        assert!(lines[3].starts_with("[1.000000, \"o\", "));
+        assert_eq!(lines.len(), 4);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Seek, Write},
+};
+
+use crate::{
+    framebuffer::Framebuffer,
+    target::{RenderTarget, html::frame_to_html, text::frame_to_text},
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DumpFormat {
+    /// Characters only, with styling stripped
+    Text,
+    /// Standalone HTML page
+    Html,
+}
+
+impl DumpFormat {
+    pub fn format(self, framebuffer: &Framebuffer) -> String {
+        match self {
+            DumpFormat::Text => frame_to_text(framebuffer),
+            DumpFormat::Html => frame_to_html(framebuffer),
+        }
+    }
+}
+
+/// Output a dump is written to, which can be emptied so a newer frame replaces the dump
+pub trait DumpWriter: Write {
+    /// Discard everything written so far
+    fn truncate(&mut self) -> io::Result<()>;
+}
+
+impl DumpWriter for File {
+    fn truncate(&mut self) -> io::Result<()> {
+        self.rewind()?;
+        self.set_len(0)
+    }
+}
+
+impl<W: DumpWriter> DumpWriter for BufWriter<W> {
+    fn truncate(&mut self) -> io::Result<()> {
+        self.flush()?;
+        self.get_mut().truncate()
+    }
+}
+
+impl DumpWriter for Vec<u8> {
+    fn truncate(&mut self) -> io::Result<()> {
+        self.clear();
+        Ok(())
+    }
+}
+
+/// Writes a single frame as text or HTML, for golden files and bug reports
+pub struct DumpTarget<W: DumpWriter> {
+    writer: W,
+    format: DumpFormat,
+    /// Index of the frame to dump. If None, every frame replaces the previous one, so the last
+    /// frame is left even if the process is killed.
+    frame: Option<u64>,
+    frame_count: u64,
+    is_written: bool,
+}
+
+impl<W: DumpWriter> DumpTarget<W> {
+    pub fn new(writer: W, format: DumpFormat, frame: Option<u64>) -> DumpTarget<W> {
+        DumpTarget {
+            writer,
+            format,
+            frame,
+            frame_count: 0,
+            is_written: false,
+        }
+    }
+
+    fn write(&mut self, dump: &str) -> io::Result<()> {
+        self.is_written = true;
+        self.writer.write_all(dump.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+impl<W: DumpWriter> RenderTarget for DumpTarget<W> {
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()> {
+        let frame_index = self.frame_count;
+
+        self.frame_count += 1;
+
+        match self.frame {
+            _ if self.is_written => Ok(()),
+            Some(frame) if frame == frame_index => self.write(&self.format.format(framebuffer)),
+            Some(_) => Ok(()),
+            None => {
+                self.writer.truncate()?;
+                self.writer
+                    .write_all(self.format.format(framebuffer).as_bytes())?;
+                self.writer.flush()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{target::point_frame, terminal::Color};
+
+    #[test]
+    fn dump_latest_frame() {
+        let mut framebuffer = point_frame((4, 3), Color::Red);
+        let mut target = DumpTarget::new(vec![], DumpFormat::Text, None);
+
+        target.present(&framebuffer).unwrap();
+        assert_eq!(target.writer, b"\n  +\n\n");
+
+        // Each frame replaces the last, without waiting for rendering to finish
+        framebuffer.clear();
+        target.present(&framebuffer).unwrap();
+        assert_eq!(target.writer, b"\n\n\n");
+    }
+}
@@ -0,0 +1,317 @@
+use crate::subcell::SubCellMode;
+
+/// Width of a cell in the exported image, in pixels
+pub const CELL_WIDTH: usize = 6;
+/// Height of a cell in the exported image, in pixels
+pub const CELL_HEIGHT: usize = 12;
+
+/// Offset of ASCII glyphs within the cell, (x, y)
+const GLYPH_OFFSET: (usize, usize) = (0, 2);
+
+/// 5x8 glyphs for printable ASCII, starting at ' '. Each byte is a row, where bit 4 is the leftmost
+/// pixel. The last row is for descenders.
+const ASCII_GLYPHS: [[u8; 8]; 95] = [
+    // ' '
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // '!'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04, 0x00],
+    // '"'
+    [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // '#'
+    [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A, 0x00],
+    // '$'
+    [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04, 0x00],
+    // '%'
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00],
+    // '&'
+    [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D, 0x00],
+    // '\''
+    [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // '('
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00],
+    // ')'
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00],
+    // '*'
+    [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00, 0x00],
+    // '+'
+    [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x00],
+    // ','
+    [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08, 0x00],
+    // '-'
+    [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00],
+    // '.'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00],
+    // '/'
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00],
+    // '0'
+    [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E, 0x00],
+    // '1'
+    [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00],
+    // '2'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F, 0x00],
+    // '3'
+    [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E, 0x00],
+    // '4'
+    [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02, 0x00],
+    // '5'
+    [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E, 0x00],
+    // '6'
+    [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E, 0x00],
+    // '7'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00],
+    // '8'
+    [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E, 0x00],
+    // '9'
+    [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C, 0x00],
+    // ':'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00, 0x00],
+    // ';'
+    [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08, 0x00],
+    // '<'
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00],
+    // '='
+    [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00, 0x00],
+    // '>'
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00],
+    // '?'
+    [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00],
+    // '@'
+    [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E, 0x00],
+    // 'A'
+    [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x00],
+    // 'B'
+    [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E, 0x00],
+    // 'C'
+    [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E, 0x00],
+    // 'D'
+    [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C, 0x00],
+    // 'E'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F, 0x00],
+    // 'F'
+    [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10, 0x00],
+    // 'G'
+    [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F, 0x00],
+    // 'H'
+    [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11, 0x00],
+    // 'I'
+    [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00],
+    // 'J'
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C, 0x00],
+    // 'K'
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00],
+    // 'L'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F, 0x00],
+    // 'M'
+    [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00],
+    // 'N'
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00],
+    // 'O'
+    [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00],
+    // 'P'
+    [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00],
+    // 'Q'
+    [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D, 0x00],
+    // 'R'
+    [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11, 0x00],
+    // 'S'
+    [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E, 0x00],
+    // 'T'
+    [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00],
+    // 'U'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00],
+    // 'V'
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00],
+    // 'W'
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A, 0x00],
+    // 'X'
+    [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11, 0x00],
+    // 'Y'
+    [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x00],
+    // 'Z'
+    [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F, 0x00],
+    // '['
+    [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E, 0x00],
+    // '\\'
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00],
+    // ']'
+    [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E, 0x00],
+    // '^'
+    [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // '_'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00],
+    // '`'
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00],
+    // 'a'
+    [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00],
+    // 'b'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E, 0x00],
+    // 'c'
+    [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E, 0x00],
+    // 'd'
+    [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F, 0x00],
+    // 'e'
+    [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00],
+    // 'f'
+    [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08, 0x00],
+    // 'g'
+    [0x00, 0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E],
+    // 'h'
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00],
+    // 'i'
+    [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00],
+    // 'j'
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x12, 0x0C],
+    // 'k'
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00],
+    // 'l'
+    [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00],
+    // 'm'
+    [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11, 0x00],
+    // 'n'
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00],
+    // 'o'
+    [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00],
+    // 'p'
+    [0x00, 0x00, 0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10],
+    // 'q'
+    [0x00, 0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x01],
+    // 'r'
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00],
+    // 's'
+    [0x00, 0x00, 0x0F, 0x10, 0x0E, 0x01, 0x1E, 0x00],
+    // 't'
+    [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06, 0x00],
+    // 'u'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00],
+    // 'v'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00],
+    // 'w'
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A, 0x00],
+    // 'x'
+    [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00],
+    // 'y'
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0F, 0x01, 0x0E],
+    // 'z'
+    [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F, 0x00],
+    // '{'
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00],
+    // '|'
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00],
+    // '}'
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00],
+    // '~'
+    [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00, 0x00],
+];
+
+/// Bitmap of a cell, row by row. True where the foreground color shows.
+pub type CellBitmap = [[bool; CELL_WIDTH]; CELL_HEIGHT];
+
+/// Fill the pixels of `bitmap` covered by the sub-cell pixels set in `mask`. Braille dots are
+/// drawn as small squares rather than filling their whole area.
+fn rasterize_subcell(bitmap: &mut CellBitmap, mode: SubCellMode, mask: u8) {
+    let (scale_x, scale_y) = mode.scale();
+    let (scale_x, scale_y) = (scale_x as usize, scale_y as usize);
+
+    for row in 0..scale_y {
+        for col in 0..scale_x {
+            if mask & (1 << (row * scale_x + col)) == 0 {
+                continue;
+            }
+
+            let (x0, x1) = (col * CELL_WIDTH / scale_x, (col + 1) * CELL_WIDTH / scale_x);
+            let (y0, y1) = (
+                row * CELL_HEIGHT / scale_y,
+                (row + 1) * CELL_HEIGHT / scale_y,
+            );
+            let (x0, x1, y0, y1) = if mode == SubCellMode::Braille {
+                (x0, x1 - 1, y0 + 1, y1)
+            } else {
+                (x0, x1, y0, y1)
+            };
+
+            for line in &mut bitmap[y0..y1] {
+                line[x0..x1].fill(true);
+            }
+        }
+    }
+}
+
+/// Fill the pixels of `bitmap` along the box drawing line `character`, if it is one. Lines span the
+/// whole cell so they join up with the lines in neighbouring cells.
+fn rasterize_line(bitmap: &mut CellBitmap, character: char) -> bool {
+    for (y, line) in bitmap.iter_mut().enumerate() {
+        let x = match character {
+            '─' if y == CELL_HEIGHT / 2 => {
+                line.fill(true);
+                continue;
+            }
+            '─' => continue,
+            '│' => CELL_WIDTH / 2,
+            '╱' => (CELL_HEIGHT - 1 - y) * CELL_WIDTH / CELL_HEIGHT,
+            '╲' => y * CELL_WIDTH / CELL_HEIGHT,
+            _ => return false,
+        };
+
+        line[x] = true;
+    }
+
+    true
+}
+
+/// Draw `character` into a cell. Characters without a glyph are drawn as `?`.
+pub fn rasterize(character: char) -> CellBitmap {
+    let mut bitmap = [[false; CELL_WIDTH]; CELL_HEIGHT];
+
+    if rasterize_line(&mut bitmap, character) {
+        return bitmap;
+    }
+
+    for mode in [
+        SubCellMode::Quadrant,
+        SubCellMode::Sextant,
+        SubCellMode::Braille,
+    ] {
+        if let Some(mask) = mode.mask(character) {
+            rasterize_subcell(&mut bitmap, mode, mask);
+
+            return bitmap;
+        }
+    }
+
+    let index = match character {
+        ' '..='~' => character as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+
+    for (y, row) in ASCII_GLYPHS[index].iter().enumerate() {
+        for x in 0..5 {
+            bitmap[GLYPH_OFFSET.1 + y][GLYPH_OFFSET.0 + x] = row & (0x10 >> x) != 0;
+        }
+    }
+
+    bitmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_glyphs() {
+        let count = |bitmap: CellBitmap| bitmap.iter().flatten().filter(|&&pixel| pixel).count();
+
+        assert_eq!(rasterize('─')[CELL_HEIGHT / 2], [true; CELL_WIDTH]);
+        assert_eq!(count(rasterize('─')), CELL_WIDTH);
+        assert!(rasterize('│').iter().all(|line| line[CELL_WIDTH / 2]));
+        assert_eq!(count(rasterize('│')), CELL_HEIGHT);
+        // Diagonals run corner to corner
+        let rising = rasterize('╱');
+        assert!(rising[CELL_HEIGHT - 1][0] && rising[0][CELL_WIDTH - 1]);
+        let falling = rasterize('╲');
+        assert!(falling[0][0] && falling[CELL_HEIGHT - 1][CELL_WIDTH - 1]);
+        // None of them fall back to '?'
+        for character in ['─', '│', '╱', '╲'] {
+            assert_ne!(rasterize(character), rasterize('?'));
+        }
+    }
+}
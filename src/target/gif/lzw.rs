@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+/// Largest code allowed by the GIF format
+const MAX_CODE: u16 = 4095;
+
+/// Packs variable width codes into bytes, least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bit_count: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bit_count;
+        self.bit_count += width;
+
+        while self.bit_count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+/// Compress `data` with the variable code width LZW variant used by GIF. Every value in `data`
+/// must fit in `min_code_size` bits.
+pub fn lzw_encode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear_code = 1u16 << min_code_size;
+    let end_code = clear_code + 1;
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bit_count: 0,
+    };
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end_code + 1;
+
+    writer.write(clear_code, code_size);
+
+    let Some((&first, rest)) = data.split_first() else {
+        writer.write(end_code, code_size);
+        return writer.finish();
+    };
+    let mut prefix = first as u16;
+
+    for &value in rest {
+        if let Some(&code) = dictionary.get(&(prefix, value)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+        // The decoder widens codes once its table reaches the current width, which happens one
+        // code later than the encoder's
+        if next_code >= 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+
+        if next_code <= MAX_CODE {
+            dictionary.insert((prefix, value), next_code);
+            next_code += 1;
+        } else {
+            writer.write(clear_code, code_size);
+            dictionary.clear();
+            code_size = min_code_size + 1;
+            next_code = end_code + 1;
+        }
+
+        prefix = value as u16;
+    }
+
+    writer.write(prefix, code_size);
+    if next_code >= 1 << code_size && code_size < 12 {
+        code_size += 1;
+    }
+    writer.write(end_code, code_size);
+
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference decoder, following the GIF specification
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear_code = 1u16 << min_code_size;
+        let end_code = clear_code + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut code_size = min_code_size + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut output = Vec::new();
+        let (mut buffer, mut bit_count, mut position) = (0u32, 0u8, 0);
+
+        loop {
+            while bit_count < code_size {
+                buffer |= (bytes[position] as u32) << bit_count;
+                position += 1;
+                bit_count += 8;
+            }
+
+            let code = (buffer & ((1 << code_size) - 1)) as u16;
+            buffer >>= code_size;
+            bit_count -= code_size;
+
+            if code == clear_code {
+                table = (0..clear_code).map(|value| vec![value as u8]).collect();
+                table.push(vec![]);
+                table.push(vec![]);
+                code_size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end_code {
+                return output;
+            }
+
+            let entry = if (code as usize) < table.len() {
+                table[code as usize].clone()
+            } else {
+                let mut entry = prev.clone().unwrap();
+                entry.push(entry[0]);
+                entry
+            };
+
+            if let Some(mut prev) = prev
+                && table.len() <= MAX_CODE as usize
+            {
+                prev.push(entry[0]);
+                table.push(prev);
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+
+            output.extend(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let short = vec![1, 1, 1, 1, 2, 2, 3, 1, 1, 1, 1];
+        // Long and varied enough to fill the dictionary and force a clear code
+        let long: Vec<u8> = (0..100_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8 % 7 + (i / 1000 % 3) as u8)
+            .collect();
+
+        assert_eq!(lzw_decode(&lzw_encode(&short, 2), 2), short);
+        assert_eq!(lzw_decode(&lzw_encode(&long, 8), 8), long);
+        assert_eq!(lzw_decode(&lzw_encode(&[], 8), 8), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,238 @@
+pub mod font;
+pub mod lzw;
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    framebuffer::Framebuffer,
+    target::{
+        RenderTarget,
+        gif::{
+            font::{CELL_HEIGHT, CELL_WIDTH, CellBitmap},
+            lzw::lzw_encode,
+        },
+    },
+    terminal::{Color, ColorSupport, Decor},
+};
+
+/// Palette index used for the default foreground color
+const DEFAULT_FOREGROUND: u8 = 7;
+/// Palette index used for the default background color
+const DEFAULT_BACKGROUND: u8 = 0;
+
+/// Index of `color` in the 256-color palette, which is used as the GIF's color table
+fn palette_index(color: Color, is_high_intensity: bool, default: u8) -> u8 {
+    let basic_index = |num: u8| if is_high_intensity { num + 8 } else { num };
+
+    match color.downgrade(ColorSupport::Indexed) {
+        Color::Reset => default,
+        Color::Black => basic_index(0),
+        Color::Red => basic_index(1),
+        Color::Green => basic_index(2),
+        Color::Yellow => basic_index(3),
+        Color::Blue => basic_index(4),
+        Color::Purple => basic_index(5),
+        Color::Cyan => basic_index(6),
+        Color::White => basic_index(7),
+        Color::Indexed(index) => index,
+        Color::Rgb(..) => unreachable!("Downgraded colors are never Rgb"),
+    }
+}
+
+/// Writes frames as an animated GIF, drawing each cell with a built-in bitmap font. The image size
+/// is fixed by the first frame. Later frames of a different size are cropped or padded.
+pub struct GifTarget<W: Write> {
+    writer: W,
+    /// Time between frames, in hundredths of a second
+    frame_delay: f64,
+    /// Size in cells, None until the header is written
+    size: Option<(u16, u16)>,
+    frame_count: u64,
+    glyphs: HashMap<char, CellBitmap>,
+    is_finished: bool,
+}
+
+impl<W: Write> GifTarget<W> {
+    pub fn new(writer: W, fps: u16) -> GifTarget<W> {
+        GifTarget {
+            writer,
+            frame_delay: 100.0 / fps as f64,
+            size: None,
+            frame_count: 0,
+            glyphs: HashMap::new(),
+            is_finished: false,
+        }
+    }
+
+    fn write_header(&mut self, size: (u16, u16)) -> io::Result<()> {
+        let width = size.0 as usize * CELL_WIDTH;
+        let height = size.1 as usize * CELL_HEIGHT;
+
+        self.writer.write_all(b"GIF89a")?;
+        // Logical screen descriptor, with a 256 entry global color table
+        self.writer.write_all(&(width as u16).to_le_bytes())?;
+        self.writer.write_all(&(height as u16).to_le_bytes())?;
+        self.writer.write_all(&[0xF7, DEFAULT_BACKGROUND, 0])?;
+
+        for index in 0..=255 {
+            let (r, g, b) = Color::palette_rgb(index);
+
+            self.writer.write_all(&[r, g, b])?;
+        }
+
+        // Loop forever
+        self.writer
+            .write_all(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00")
+    }
+
+    /// Delay after the current frame, in hundredths of a second. Rounding errors are carried over
+    /// to later frames, so the average frame rate stays exact.
+    fn get_delay(&self) -> u16 {
+        let start = (self.frame_count as f64 * self.frame_delay).round();
+        let end = ((self.frame_count + 1) as f64 * self.frame_delay).round();
+
+        (end - start) as u16
+    }
+
+    /// Convert a frame to palette indices, row by row
+    fn rasterize(&mut self, framebuffer: &Framebuffer, size: (u16, u16)) -> Vec<u8> {
+        let (frame_width, frame_height) = framebuffer.get_size();
+        let width = size.0 as usize * CELL_WIDTH;
+        let mut pixels = vec![DEFAULT_BACKGROUND; width * size.1 as usize * CELL_HEIGHT];
+
+        for row in 0..size.1.min(frame_height) as usize {
+            for col in 0..size.0.min(frame_width) as usize {
+                let cell = framebuffer.get_cell(col, row);
+                let is_high_intensity =
+                    cell.decor == Decor::HighIntensity || cell.decor == Decor::BoldHighIntensity;
+                let foreground = palette_index(cell.color, is_high_intensity, DEFAULT_FOREGROUND);
+                let background = palette_index(cell.background, false, DEFAULT_BACKGROUND);
+                let bitmap = self
+                    .glyphs
+                    .entry(cell.character)
+                    .or_insert_with(|| font::rasterize(cell.character));
+
+                for (y, bitmap_row) in bitmap.iter().enumerate() {
+                    let is_underline = cell.decor == Decor::Underline && y == CELL_HEIGHT - 1;
+                    let start = (row * CELL_HEIGHT + y) * width + col * CELL_WIDTH;
+
+                    for (x, &is_set) in bitmap_row.iter().enumerate() {
+                        pixels[start + x] = if is_set || is_underline {
+                            foreground
+                        } else {
+                            background
+                        };
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+impl<W: Write> RenderTarget for GifTarget<W> {
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()> {
+        let size = match self.size {
+            Some(size) => size,
+            None => {
+                let size = framebuffer.get_size();
+
+                self.write_header(size)?;
+                self.size = Some(size);
+                size
+            }
+        };
+        let pixels = self.rasterize(framebuffer, size);
+
+        // Graphic control extension, so the frame is shown for the right amount of time
+        self.writer.write_all(&[0x21, 0xF9, 0x04, 0x04])?;
+        self.writer.write_all(&self.get_delay().to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+
+        // Image descriptor, covering the whole screen
+        self.writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.writer
+            .write_all(&((size.0 as usize * CELL_WIDTH) as u16).to_le_bytes())?;
+        self.writer
+            .write_all(&((size.1 as usize * CELL_HEIGHT) as u16).to_le_bytes())?;
+        self.writer.write_all(&[0])?;
+
+        // Image data, split into blocks of at most 255 bytes
+        self.writer.write_all(&[8])?;
+        for block in lzw_encode(&pixels, 8).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0])?;
+
+        self.frame_count += 1;
+
+        // Write out each frame as soon as it is complete. The trailer is only written by `finish`,
+        // so a recording that is interrupted ends without one.
+        self.writer.flush()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.is_finished || self.size.is_none() {
+            return Ok(());
+        }
+
+        self.is_finished = true;
+        self.writer.write_all(&[0x3B])?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subcell::SubCellMode;
+
+    /// Skip a sequence of data sub-blocks, which ends with an empty block
+    fn skip_sub_blocks(gif: &[u8], mut offset: usize) -> usize {
+        while gif[offset] != 0 {
+            offset += gif[offset] as usize + 1;
+        }
+
+        offset + 1
+    }
+
+    #[test]
+    fn gif_container() {
+        let mut target = GifTarget::new(Vec::new(), 10);
+        let framebuffer = Framebuffer::new((2, 1), SubCellMode::Character);
+
+        for _ in 0..3 {
+            target.present(&framebuffer).unwrap();
+        }
+        target.finish().unwrap();
+        target.finish().unwrap();
+
+        let gif = &target.writer;
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..8], &(2 * CELL_WIDTH as u16).to_le_bytes());
+        assert_eq!(&gif[8..10], &(CELL_HEIGHT as u16).to_le_bytes());
+
+        // Walk the blocks after the header and the global color table
+        let mut offset = 13 + 256 * 3;
+        let mut frame_count = 0;
+
+        while gif[offset] != 0x3B {
+            match gif[offset] {
+                0x21 => offset = skip_sub_blocks(gif, offset + 2),
+                0x2C => {
+                    frame_count += 1;
+                    offset = skip_sub_blocks(gif, offset + 11);
+                }
+                block => panic!("Unexpected block {:#x}", block),
+            }
+        }
+
+        assert_eq!(frame_count, 3);
+        assert_eq!(offset, gif.len() - 1);
+    }
+}
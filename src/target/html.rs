@@ -0,0 +1,212 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use crate::{
+    framebuffer::Framebuffer,
+    terminal::{Cell, Color, Decor},
+};
+
+/// Default foreground and background colors of the page
+const DEFAULT_COLORS: ((u8, u8, u8), (u8, u8, u8)) = ((229, 229, 229), (0, 0, 0));
+
+fn hex_color((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Class name of a named color, and its index in the 16-color palette
+fn named_color(color: Color) -> Option<(&'static str, u8)> {
+    match color {
+        Color::Black => Some(("black", 0)),
+        Color::Red => Some(("red", 1)),
+        Color::Green => Some(("green", 2)),
+        Color::Yellow => Some(("yellow", 3)),
+        Color::Blue => Some(("blue", 4)),
+        Color::Purple => Some(("purple", 5)),
+        Color::Cyan => Some(("cyan", 6)),
+        Color::White => Some(("white", 7)),
+        _ => None,
+    }
+}
+
+fn decor_class(decor: Decor) -> Option<&'static str> {
+    match decor {
+        Decor::None => None,
+        Decor::Bold => Some("bold"),
+        Decor::Underline => Some("underline"),
+        Decor::HighIntensity => Some("high-intensity"),
+        Decor::BoldHighIntensity => Some("bold-high-intensity"),
+    }
+}
+
+/// Class for `color`, with `layer` being `fg` or `bg`. Truecolor has no class, and is set inline.
+fn color_class(color: Color, layer: &str) -> Option<String> {
+    match color {
+        Color::Reset | Color::Rgb(..) => None,
+        Color::Indexed(index) => Some(format!("{}-{}", layer, index)),
+        _ => named_color(color).map(|(name, _)| format!("{}-{}", layer, name)),
+    }
+}
+
+/// CSS rules for the color and decor classes that are used
+fn stylesheet(cells: &[Cell]) -> String {
+    let mut css = format!(
+        "pre.terminal-3d {{ color: {}; background: {}; line-height: 1.2; }}\n",
+        hex_color(DEFAULT_COLORS.0),
+        hex_color(DEFAULT_COLORS.1)
+    );
+    let mut indexed = BTreeSet::new();
+
+    css.push_str(".bold, .bold-high-intensity { font-weight: bold; }\n");
+    css.push_str(".underline { text-decoration: underline; }\n");
+    for color in [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Purple,
+        Color::Cyan,
+        Color::White,
+    ] {
+        let (name, index) = named_color(color).unwrap();
+        let normal = hex_color(Color::palette_rgb(index));
+        let bright = hex_color(Color::palette_rgb(index + 8));
+
+        writeln!(css, ".fg-{} {{ color: {}; }}", name, normal).unwrap();
+        writeln!(
+            css,
+            ".high-intensity.fg-{0}, .bold-high-intensity.fg-{0} {{ color: {1}; }}",
+            name, bright
+        )
+        .unwrap();
+        writeln!(css, ".bg-{} {{ background: {}; }}", name, normal).unwrap();
+    }
+
+    for cell in cells {
+        for (color, layer) in [(cell.color, "fg"), (cell.background, "bg")] {
+            if let Color::Indexed(index) = color {
+                indexed.insert((index, layer));
+            }
+        }
+    }
+    for (index, layer) in indexed {
+        let property = if layer == "fg" { "color" } else { "background" };
+
+        writeln!(
+            css,
+            ".{}-{} {{ {}: {}; }}",
+            layer,
+            index,
+            property,
+            hex_color(Color::palette_rgb(index))
+        )
+        .unwrap();
+    }
+
+    css
+}
+
+/// Opening tag for a run of cells styled like `cell`, or None if the cell is unstyled
+fn span_tag(cell: Cell) -> Option<String> {
+    let classes: Vec<String> = [
+        color_class(cell.color, "fg"),
+        color_class(cell.background, "bg"),
+        decor_class(cell.decor).map(String::from),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let mut styles = Vec::new();
+
+    if let Color::Rgb(r, g, b) = cell.color {
+        styles.push(format!("color: {}", hex_color((r, g, b))));
+    }
+    if let Color::Rgb(r, g, b) = cell.background {
+        styles.push(format!("background: {}", hex_color((r, g, b))));
+    }
+
+    if classes.is_empty() && styles.is_empty() {
+        return None;
+    }
+
+    let mut tag = String::from("<span");
+
+    if !classes.is_empty() {
+        write!(tag, " class=\"{}\"", classes.join(" ")).unwrap();
+    }
+    if !styles.is_empty() {
+        write!(tag, " style=\"{}\"", styles.join("; ")).unwrap();
+    }
+
+    Some(tag + ">")
+}
+
+fn escape_html(character: char, html: &mut String) {
+    match character {
+        '&' => html.push_str("&amp;"),
+        '<' => html.push_str("&lt;"),
+        '>' => html.push_str("&gt;"),
+        _ => html.push(character),
+    }
+}
+
+/// A frame as a standalone HTML page. Cells are wrapped in `<span>`s whose classes are named after
+/// `terminal::Color` and `terminal::Decor`, such as `fg-red`, `bg-208` and `bold`.
+pub fn frame_to_html(framebuffer: &Framebuffer) -> String {
+    let (width, height) = framebuffer.get_size();
+    let cells = framebuffer.get_cells();
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n{}</style>\n</head>\n<body>\n<pre class=\"terminal-3d\">",
+        stylesheet(&cells)
+    );
+
+    for row in cells.chunks(width as usize).take(height as usize) {
+        let mut current_style: Option<(Color, Color, Decor)> = None;
+        let mut is_span_open = false;
+
+        for &cell in row {
+            let style = (cell.color, cell.background, cell.decor);
+
+            if current_style != Some(style) {
+                if is_span_open {
+                    html.push_str("</span>");
+                }
+
+                let tag = span_tag(cell);
+
+                is_span_open = tag.is_some();
+                if let Some(tag) = tag {
+                    html.push_str(&tag);
+                }
+                current_style = Some(style);
+            }
+
+            escape_html(cell.character, &mut html);
+        }
+
+        if is_span_open {
+            html.push_str("</span>");
+        }
+        html.push('\n');
+    }
+
+    html.push_str("</pre>\n</body>\n</html>\n");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target::point_frame;
+
+    #[test]
+    fn html_dump() {
+        let framebuffer = point_frame((4, 1), Color::Rgb(255, 0, 0));
+        let html = frame_to_html(&framebuffer);
+
+        assert!(html.contains(
+            "<pre class=\"terminal-3d\">  <span class=\"bold-high-intensity\" style=\"color: #ff0000\">+</span> \n</pre>"
+        ));
+        assert!(html.contains(".fg-red { color: #cd0000; }"));
+    }
+}
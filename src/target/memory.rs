@@ -0,0 +1,57 @@
+use std::io;
+
+use crate::{framebuffer::Framebuffer, target::RenderTarget, terminal::Cell};
+
+/// Keeps the most recent frame in memory, for rendering without a terminal
+pub struct MemoryTarget {
+    width: u16,
+    height: u16,
+    cells: Vec<Cell>,
+    frame_count: u64,
+}
+
+impl MemoryTarget {
+    pub fn new() -> MemoryTarget {
+        MemoryTarget {
+            width: 0,
+            height: 0,
+            cells: vec![],
+            frame_count: 0,
+        }
+    }
+
+    /// Size of the most recent frame in cells, (width, height)
+    pub fn get_size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Cells of the most recent frame, row by row
+    pub fn get_cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn get_cell(&self, col: usize, row: usize) -> Cell {
+        self.cells[row * self.width as usize + col]
+    }
+
+    /// Number of frames presented so far
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_count
+    }
+}
+
+impl Default for MemoryTarget {
+    fn default() -> MemoryTarget {
+        MemoryTarget::new()
+    }
+}
+
+impl RenderTarget for MemoryTarget {
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()> {
+        (self.width, self.height) = framebuffer.get_size();
+        self.cells = framebuffer.get_cells();
+        self.frame_count += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+pub mod ansi;
+pub mod asciicast;
+pub mod dump;
+pub mod gif;
+pub mod html;
+pub mod memory;
+pub mod text;
+pub mod writer;
+
+pub use crate::target::ansi::AnsiEncoder;
+pub use crate::target::asciicast::AsciicastTarget;
+pub use crate::target::dump::{DumpFormat, DumpTarget};
+pub use crate::target::gif::GifTarget;
+pub use crate::target::memory::MemoryTarget;
+pub use crate::target::writer::WriterTarget;
+
+use std::io;
+
+use crate::framebuffer::Framebuffer;
+
+/// Somewhere finished frames are sent, such as the terminal, a file or memory
+pub trait RenderTarget {
+    /// Output the frame currently in `framebuffer`
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()>;
+
+    /// Called once no more frames will be presented, to write anything that ends the output
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Frame `size` cells large, with a point of `color` at the origin seen from an isometric camera
+#[cfg(test)]
+pub fn point_frame(size: (u16, u16), color: crate::terminal::Color) -> Framebuffer {
+    use crate::{camera::IsoCamera, shapes::Point, subcell::SubCellMode, vector3::Vector3};
+
+    let mut framebuffer = Framebuffer::new(size, SubCellMode::Character);
+    let camera = IsoCamera::default(framebuffer.get_screen_size());
+
+    framebuffer.buffer_world_object(0, &Point::new(Vector3::zero(), color), &camera, &[], 1);
+    framebuffer
+}
@@ -0,0 +1,32 @@
+use crate::framebuffer::Framebuffer;
+
+/// The characters of a frame with all styling stripped, one line per row. Trailing spaces are
+/// removed, so dumps can be compared with plain diffs.
+pub fn frame_to_text(framebuffer: &Framebuffer) -> String {
+    let (width, height) = framebuffer.get_size();
+    let mut text = String::new();
+
+    for row in 0..height as usize {
+        let line: String = (0..width as usize)
+            .map(|col| framebuffer.get_cell(col, row).character)
+            .collect();
+
+        text.push_str(line.trim_end_matches(' '));
+        text.push('\n');
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{target::point_frame, terminal::Color};
+
+    #[test]
+    fn text_dump() {
+        let framebuffer = point_frame((4, 3), Color::Red);
+
+        assert_eq!(frame_to_text(&framebuffer), "\n  +\n\n");
+    }
+}
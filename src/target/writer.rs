@@ -0,0 +1,43 @@
+use std::io::{self, Write};
+
+use crate::{
+    framebuffer::Framebuffer,
+    target::{AnsiEncoder, RenderTarget},
+    terminal::ColorSupport,
+};
+
+/// Writes frames as ANSI escape sequences to any `io::Write`, such as stdout, a pipe or a file
+pub struct WriterTarget<W: Write> {
+    writer: W,
+    encoder: AnsiEncoder,
+}
+
+impl<W: Write> WriterTarget<W> {
+    pub fn new(writer: W, color_support: ColorSupport) -> WriterTarget<W> {
+        WriterTarget {
+            writer,
+            encoder: AnsiEncoder::new(color_support),
+        }
+    }
+
+    pub fn get_encoder(&self) -> &AnsiEncoder {
+        &self.encoder
+    }
+
+    pub fn get_encoder_mut(&mut self) -> &mut AnsiEncoder {
+        &mut self.encoder
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> RenderTarget for WriterTarget<W> {
+    fn present(&mut self, framebuffer: &Framebuffer) -> io::Result<()> {
+        let output = self.encoder.encode(framebuffer);
+
+        self.writer.write_all(output.as_bytes())?;
+        self.writer.flush()
+    }
+}
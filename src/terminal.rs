@@ -1,17 +1,30 @@
-use std::io::{self, IsTerminal, Write};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    env,
+    io::{self, IsTerminal, Write},
+    panic,
+    rc::Rc,
+    sync::{
+        Once,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 use libc;
 
 use crate::{
     camera::Camera,
-    render::{bounding_box_triangle_3d, bresenham_line_3d},
-    vector3,
-    vector3::Vector3,
+    framebuffer::{Framebuffer, Stipple},
+    lighting::Light,
+    render_mode::RenderMode,
+    subcell::SubCellMode,
+    target::{RenderTarget, WriterTarget},
     world_object::WorldObject,
 };
 
 // Adapted from https://stackoverflow.com/a/28938235/12370337
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Color {
     Reset,
     Black,
@@ -22,9 +35,13 @@ pub enum Color {
     Purple,
     Cyan,
     White,
+    /// 24-bit truecolor
+    Rgb(u8, u8, u8),
+    /// Index into the xterm 256-color palette
+    Indexed(u8),
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Decor {
     None,
     Bold,
@@ -35,70 +52,218 @@ pub enum Decor {
 
 pub type Style = (char, Color, Decor);
 
-fn get_style_escape(style: Style) -> String {
-    if style.1 == Color::Reset {
-        return String::from("\x1b[0m");
+/// Colors the output is able to display. Colors the output can't display are replaced with the
+/// closest match it can.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum ColorSupport {
+    /// The 8 basic colors and their high intensity variants
+    Basic,
+    /// The xterm 256-color palette
+    Indexed,
+    /// 24-bit truecolor
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Guess color support from the `COLORTERM` and `TERM` environment variables
+    pub fn detect() -> ColorSupport {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Indexed
+        } else {
+            ColorSupport::Basic
+        }
     }
+}
 
-    let color_num_1 = if style.2 == Decor::HighIntensity || style.2 == Decor::BoldHighIntensity {
-        9
-    } else {
-        3
-    };
-    let color_num_2 = match style.1 {
-        Color::Reset => -1,
-        Color::Black => 0,
-        Color::Red => 1,
-        Color::Green => 2,
-        Color::Yellow => 3,
-        Color::Blue => 4,
-        Color::Purple => 5,
-        Color::Cyan => 6,
-        Color::White => 7,
-    };
-    let decor_num = match style.2 {
-        Decor::None => 0,
-        Decor::Bold => 1,
-        Decor::Underline => 4,
-        Decor::HighIntensity => 0,
-        Decor::BoldHighIntensity => 1,
-    };
-
-    format!(
-        "\x1b[{decor};{color1}{color2}m",
-        decor = decor_num,
-        color1 = color_num_1,
-        color2 = color_num_2
-    )
+/// RGB values of the first 16 palette entries, as displayed by xterm
+const BASIC_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel values of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+
+    dr * dr + dg * dg + db * db
 }
 
-/// Lower number -> higher priority
-#[derive(Clone, Copy, PartialEq, PartialOrd)]
-enum DrawType {
-    Vertex,
-    Edge,
-    Face,
-    None,
+/// Index of the closest color cube level to a channel value
+fn nearest_cube_level(value: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+        .unwrap()
+}
+
+impl Color {
+    /// RGB value of a color from the 256-color palette
+    pub fn palette_rgb(index: u8) -> (u8, u8, u8) {
+        match index {
+            0..=15 => BASIC_PALETTE[index as usize],
+            16..=231 => {
+                let i = index as usize - 16;
+
+                (
+                    CUBE_LEVELS[i / 36],
+                    CUBE_LEVELS[i / 6 % 6],
+                    CUBE_LEVELS[i % 6],
+                )
+            }
+            _ => {
+                let level = 8 + 10 * (index - 232);
+
+                (level, level, level)
+            }
+        }
+    }
+
+    /// Closest entry of the 256-color palette, excluding the first 16 colors, which vary between
+    /// terminals
+    fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+        let (r, g, b) = (
+            nearest_cube_level(rgb.0),
+            nearest_cube_level(rgb.1),
+            nearest_cube_level(rgb.2),
+        );
+        let cube_index = (16 + 36 * r + 6 * g + b) as u8;
+
+        let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+        let gray_index = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+        if color_distance(rgb, Color::palette_rgb(gray_index))
+            < color_distance(rgb, Color::palette_rgb(cube_index))
+        {
+            gray_index
+        } else {
+            cube_index
+        }
+    }
+
+    /// Closest of the first 16 entries of the 256-color palette
+    fn nearest_basic(rgb: (u8, u8, u8)) -> u8 {
+        (0..16)
+            .min_by_key(|&i| color_distance(rgb, BASIC_PALETTE[i as usize]))
+            .unwrap()
+    }
+
+    /// RGB value of the color, or None for `Reset`, which depends on the terminal
+    pub fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Reset => None,
+            Color::Black => Some(BASIC_PALETTE[0]),
+            Color::Red => Some(BASIC_PALETTE[1]),
+            Color::Green => Some(BASIC_PALETTE[2]),
+            Color::Yellow => Some(BASIC_PALETTE[3]),
+            Color::Blue => Some(BASIC_PALETTE[4]),
+            Color::Purple => Some(BASIC_PALETTE[5]),
+            Color::Cyan => Some(BASIC_PALETTE[6]),
+            Color::White => Some(BASIC_PALETTE[7]),
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+            Color::Indexed(index) => Some(Color::palette_rgb(index)),
+        }
+    }
+
+    /// Replace the color with the closest one the output can display. Named colors are always
+    /// supported.
+    pub fn downgrade(self, support: ColorSupport) -> Color {
+        match (self, support) {
+            (_, ColorSupport::TrueColor) => self,
+            (Color::Rgb(r, g, b), ColorSupport::Indexed) => {
+                Color::Indexed(Color::nearest_indexed((r, g, b)))
+            }
+            (Color::Rgb(r, g, b), ColorSupport::Basic) => {
+                Color::Indexed(Color::nearest_basic((r, g, b)))
+            }
+            (Color::Indexed(index), ColorSupport::Basic) if index >= 16 => {
+                Color::Indexed(Color::nearest_basic(Color::palette_rgb(index)))
+            }
+            _ => self,
+        }
+    }
+}
+
+/// A single terminal cell, as written to the output
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cell {
+    pub character: char,
+    pub color: Color,
+    pub background: Color,
+    pub decor: Decor,
 }
 
-#[allow(unused)]
-struct Character {
-    pub frame: u64,
-    pub style: Style,
-    pub dist: i64,
-    pub shape_id: u64,
-    pub draw_type: DrawType,
+impl Cell {
+    pub fn blank() -> Cell {
+        Cell {
+            character: ' ',
+            color: Color::Reset,
+            background: Color::Reset,
+            decor: Decor::None,
+        }
+    }
+}
+
+impl From<Style> for Cell {
+    fn from(style: Style) -> Cell {
+        Cell {
+            character: style.0,
+            color: style.1,
+            background: Color::Reset,
+            decor: style.2,
+        }
+    }
 }
 
+/// Size used when the terminal size can't be determined, (columns, rows)
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
 pub struct Terminal {
     term_width: u16,
     term_height: u16,
-    display: Vec<Character>,
+    /// Size set by the user, which takes priority over the detected size
+    fixed_size: Option<(u16, u16)>,
+    is_tty: bool,
+    framebuffer: Framebuffer,
+    output: WriterTarget<io::Stdout>,
+    /// Cameras that are kept in sync with the screen size
+    cameras: Vec<Rc<RefCell<dyn Camera>>>,
+    /// Targets that receive every rendered frame along with stdout, such as recordings
+    sinks: Vec<Box<dyn RenderTarget>>,
+    /// Events that haven't been polled yet. Only the latest resize is kept, so they don't pile up
+    /// when nothing polls them.
+    events: VecDeque<TerminalEvent>,
+}
+
+pub enum TerminalEvent {
+    /// The screen was resized. Contains the new size in pixels, (width, height).
+    Resize((u16, u16)),
 }
 
 // http://rosettacode.org/wiki/Terminal_control/Dimensions#Library:_BSD_libc
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Size {
     pub rows: libc::c_ushort,
     pub cols: libc::c_ushort,
@@ -112,81 +277,284 @@ fn get_term_size() -> Option<Size> {
     let mut size = Size { rows: 0, cols: 0 };
     let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
 
-    if result == 0 { Some(size) } else { None }
+    // Terminals that were never given a size, such as some ptys, report 0x0
+    if result == 0 && size.cols != 0 && size.rows != 0 {
+        Some(size)
+    } else {
+        None
+    }
+}
+
+/// Get size from the `COLUMNS` and `LINES` environment variables
+fn get_env_size() -> Option<Size> {
+    let cols = env::var("COLUMNS").ok()?.parse().ok()?;
+    let rows = env::var("LINES").ok()?.parse().ok()?;
+
+    if cols == 0 || rows == 0 {
+        None
+    } else {
+        Some(Size { rows, cols })
+    }
+}
+
+/// Determine the size in cells, by trying the fixed size, the size reported by the terminal
+/// (`term_size`), the environment (`env_size`), then falling back to a default
+fn resolve_size(
+    fixed_size: Option<(u16, u16)>,
+    term_size: impl FnOnce() -> Option<Size>,
+    env_size: impl FnOnce() -> Option<Size>,
+) -> Size {
+    if let Some((cols, rows)) = fixed_size {
+        return Size { rows, cols };
+    }
+
+    term_size().or_else(env_size).unwrap_or(Size {
+        rows: DEFAULT_SIZE.1,
+        cols: DEFAULT_SIZE.0,
+    })
+}
+
+// \x1b[?1049h: switch to alternate screen
+// \x1b[?25l: hide cursor
+// \x1b[2J: clear screen
+const ENTER_SCREEN: &[u8] = b"\x1b[?1049h\x1b[?25l\x1b[2J";
+// \x1b[0m: reset style
+// \x1b[?25h: show cursor
+// \x1b[?1049l: switch back to original screen
+const RESTORE_SCREEN: &[u8] = b"\x1b[0m\x1b[?25h\x1b[?1049l";
+
+/// Whether the alternate screen is in use and needs to be restored
+static SCREEN_ACTIVE: AtomicBool = AtomicBool::new(false);
+/// Set by SIGWINCH, cleared once the new size has been picked up
+static RESIZED: AtomicBool = AtomicBool::new(false);
+static INSTALL_HANDLERS: Once = Once::new();
+
+/// Write bytes straight to stdout, bypassing its buffer. Safe to call from a signal handler.
+fn write_raw(bytes: &[u8]) {
+    unsafe {
+        libc::write(
+            libc::STDOUT_FILENO,
+            bytes.as_ptr() as *const libc::c_void,
+            bytes.len(),
+        );
+    }
+}
+
+/// Install the panic hook and signal handlers that restore the screen and track resizes
+fn install_handlers() {
+    INSTALL_HANDLERS.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        // Restore before the panic message is printed, so it ends up on the original screen
+        panic::set_hook(Box::new(move |info| {
+            restore_screen();
+            default_hook(info);
+        }));
+
+        let handler = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+
+        let resize_handler = handle_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+
+        unsafe {
+            libc::signal(libc::SIGINT, handler);
+            libc::signal(libc::SIGTERM, handler);
+            libc::signal(libc::SIGWINCH, resize_handler);
+        }
+    });
+}
+
+fn enter_screen() {
+    if !SCREEN_ACTIVE.swap(true, Ordering::SeqCst) {
+        write_raw(ENTER_SCREEN);
+    }
+}
+
+/// Switch back to the original screen, show the cursor and reset the style, if the alternate
+/// screen is in use
+fn restore_screen() {
+    if SCREEN_ACTIVE.swap(false, Ordering::SeqCst) {
+        write_raw(RESTORE_SCREEN);
+    }
+}
+
+extern "C" fn handle_signal(signal: libc::c_int) {
+    restore_screen();
+
+    // Let the default handler terminate the process
+    unsafe {
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
+
+extern "C" fn handle_resize(_signal: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        for sink in &mut self.sinks {
+            let _ = sink.finish();
+        }
+
+        if self.is_tty {
+            // Anything still buffered belongs on the alternate screen
+            let _ = io::stdout().flush();
+            restore_screen();
+        }
+    }
+}
+
+impl Default for Terminal {
+    fn default() -> Terminal {
+        Terminal::new()
+    }
 }
 
 impl Terminal {
     pub fn new() -> Terminal {
-        // \x1b[2J: clear screen
-        // \x1b[H: move cursor to top-left
-        print!("{esc}[2J", esc = 27 as char);
+        Terminal::create(None)
+    }
 
-        let size = get_term_size().unwrap();
+    /// Create a terminal with a fixed size in cells, (columns, rows), instead of following the
+    /// size of the actual terminal
+    pub fn with_size(size: (u16, u16)) -> Terminal {
+        Terminal::create(Some(size))
+    }
+
+    fn create(fixed_size: Option<(u16, u16)>) -> Terminal {
+        let is_tty = io::stdout().is_terminal();
+        let mut output = WriterTarget::new(io::stdout(), ColorSupport::detect());
+
+        if is_tty {
+            install_handlers();
+            enter_screen();
+        } else {
+            // Keep output that isn't going to a terminal free of clear screen escapes, so it can be
+            // captured
+            output.get_encoder_mut().set_clears_screen(false);
+        }
+
+        let size = resolve_size(fixed_size, get_term_size, get_env_size);
         Terminal {
             term_width: size.cols,
             term_height: size.rows,
-            display: vec![],
+            fixed_size,
+            is_tty,
+            framebuffer: Framebuffer::new((size.cols, size.rows), SubCellMode::Character),
+            output,
+            cameras: vec![],
+            sinks: vec![],
+            events: VecDeque::new(),
         }
     }
 
+    /// Whether stdout is a terminal
+    pub fn is_tty(&self) -> bool {
+        self.is_tty
+    }
+
+    pub fn get_color_support(&self) -> ColorSupport {
+        self.output.get_encoder().get_color_support()
+    }
+
+    /// Override the detected color support
+    pub fn set_color_support(&mut self, color_support: ColorSupport) {
+        self.output
+            .get_encoder_mut()
+            .set_color_support(color_support);
+    }
+
+    pub fn get_subcell_mode(&self) -> SubCellMode {
+        self.framebuffer.get_subcell_mode()
+    }
+
+    /// Change how pixels are packed into cells. This changes the size reported by
+    /// `get_term_size` and the shape of pixels, which registered cameras are given.
+    pub fn set_subcell_mode(&mut self, subcell_mode: SubCellMode) {
+        self.framebuffer.set_subcell_mode(subcell_mode);
+        self.update_cameras();
+    }
+
+    pub fn get_render_mode(&self) -> RenderMode {
+        self.framebuffer.get_render_mode()
+    }
+
+    /// Change which parts of objects are drawn, for every object that doesn't pick its own
+    pub fn set_render_mode(&mut self, render_mode: RenderMode) {
+        self.framebuffer.set_render_mode(render_mode);
+    }
+
+    pub fn get_hidden_edges(&self) -> Stipple {
+        self.framebuffer.get_hidden_edges()
+    }
+
+    /// Draw edges hidden behind faces with `hidden_edges`, instead of leaving them out
+    pub fn set_hidden_edges(&mut self, hidden_edges: Stipple) {
+        self.framebuffer.set_hidden_edges(hidden_edges);
+    }
+
+    /// Size of the screen in pixels, (width, height)
     pub fn get_term_size(&self) -> (u16, u16) {
-        (self.term_width, self.term_height * 2) // Report height as doubled
+        self.framebuffer.get_screen_size()
     }
 
-    // Plot character, assuming x and y are in bounds
-    fn plot_character(
-        &mut self,
-        x: u16,
-        y: u16,
-        depth: i64,
-        style: Style,
-        shape_id: u64,
-        draw_type: DrawType,
-        frame: u64,
-    ) {
-        // y coordinate should be halved, because monospace characters 2x as tall as they are wide
-        let index = (y as f32 / 2.0).floor() as usize * self.term_width as usize + x as usize;
-        let cur_pixel = &self.display[index];
-
-        let is_same_frame = cur_pixel.frame == frame;
-        let is_drawtype_none = cur_pixel.draw_type == DrawType::None;
-        let is_in_front = cur_pixel.dist > depth;
-
-        if !is_same_frame || is_drawtype_none || is_in_front {
-            self.display[index] = Character {
-                frame,
-                style: style,
-                dist: depth,
-                shape_id,
-                draw_type,
-            }
+    pub fn get_framebuffer(&self) -> &Framebuffer {
+        &self.framebuffer
+    }
+
+    pub fn get_framebuffer_mut(&mut self) -> &mut Framebuffer {
+        &mut self.framebuffer
+    }
+
+    /// Keep `camera`'s screen size in sync with the terminal, including after resizes and sub-cell
+    /// mode changes
+    pub fn register_camera(&mut self, camera: Rc<RefCell<dyn Camera>>) {
+        {
+            let mut camera = camera.borrow_mut();
+
+            camera.update_screen_size(self.get_term_size());
+            camera.update_pixel_aspect(self.get_subcell_mode().pixel_aspect());
+        }
+        self.cameras.push(camera);
+    }
+
+    fn update_cameras(&mut self) {
+        let screen_size = self.get_term_size();
+        let pixel_aspect = self.get_subcell_mode().pixel_aspect();
+
+        for camera in &self.cameras {
+            let mut camera = camera.borrow_mut();
+
+            camera.update_screen_size(screen_size);
+            camera.update_pixel_aspect(pixel_aspect);
         }
     }
 
-    fn is_in_bounds(&self, x: i64, y: i64) -> bool {
-        x >= 0 && (x as u16) < self.term_width && y >= 0 && y < self.term_height as i64 * 2
+    /// Take the oldest event that hasn't been handled yet
+    pub fn poll_event(&mut self) -> Option<TerminalEvent> {
+        self.events.pop_front()
     }
 
+    /// Prepare for a new frame. Picks up resizes signalled since the last frame, and clears the
+    /// framebuffer.
     pub fn pre_render(&mut self) {
-        let size = get_term_size().unwrap();
-
-        if self.term_height != size.rows || self.term_height != size.cols {
-            self.term_height = size.rows;
-            self.term_width = size.cols;
-            let char_count = size.rows as usize * size.cols as usize;
-
-            self.display = Vec::with_capacity(char_count);
-
-            for _ in 0..char_count {
-                self.display.push(Character {
-                    frame: 0,
-                    style: (' ', Color::Reset, Decor::None),
-                    dist: 0,
-                    draw_type: DrawType::None,
-                    shape_id: 0,
-                });
+        if self.fixed_size.is_none() && RESIZED.swap(false, Ordering::SeqCst) {
+            let size = resolve_size(None, get_term_size, get_env_size);
+
+            if self.term_width != size.cols || self.term_height != size.rows {
+                self.term_height = size.rows;
+                self.term_width = size.cols;
+                self.framebuffer.resize((size.cols, size.rows));
+                self.update_cameras();
+                self.events
+                    .retain(|event| !matches!(event, TerminalEvent::Resize(_)));
+                self.events
+                    .push_back(TerminalEvent::Resize(self.get_term_size()));
             }
         }
+
+        self.framebuffer.clear();
     }
 
     pub fn buffer_world_object(
@@ -194,100 +562,147 @@ impl Terminal {
         shape_id: u64,
         obj: &dyn WorldObject,
         camera: &dyn Camera,
+        lights: &[Box<dyn Light>],
         frame: u64,
     ) {
-        let vertices = obj.vectices();
-        let vertex_style = obj.vertex_style();
-        let edge_style = obj.edge_style();
-        let face_style = obj.face_style();
-
-        // Render vertices, edges, then faces
-
-        for vertex in &vertices {
-            let projection = camera.project_vector(*vertex);
-            let (x, y) = (projection.x.round() as i64, projection.y.round() as i64);
-            if self.is_in_bounds(x, y) && projection.z > 0.0 {
-                self.plot_character(
-                    x as u16,
-                    y as u16,
-                    projection.z.round() as i64,
-                    vertex_style,
-                    shape_id,
-                    DrawType::Vertex,
-                    frame,
-                );
-            }
-        }
+        self.framebuffer
+            .buffer_world_object(shape_id, obj, camera, lights, frame);
+    }
 
-        for edge in obj.edges() {
-            let start = camera.project_vector(vertices[edge.0]);
-            let end = camera.project_vector(vertices[edge.1]);
-
-            bresenham_line_3d(start, end, |pixel: (i64, i64), depth: f64| {
-                if self.is_in_bounds(pixel.0, pixel.1) && depth > 0.0 {
-                    self.plot_character(
-                        pixel.0 as u16,
-                        pixel.1 as u16,
-                        depth.round() as i64,
-                        edge_style,
-                        shape_id,
-                        DrawType::Edge,
-                        frame,
-                    );
-                }
-            });
-        }
+    /// Force the next call to `render` to clear the screen and redraw every cell
+    pub fn request_full_repaint(&mut self) {
+        self.output.get_encoder_mut().request_full_repaint();
+    }
 
-        for triangle in obj.triangles() {
-            let points = (
-                camera.project_vector(vertices[triangle.0]),
-                camera.project_vector(vertices[triangle.1]),
-                camera.project_vector(vertices[triangle.2]),
-            );
-
-            // Check direction, perform culling if needed
-            let direction =
-                ((points.1 - points.0).with_z(0.0) * (points.2 - points.0).with_z(0.0)).normalize();
-
-            if direction == vector3!(0, 0, -1) {
-                bounding_box_triangle_3d(points, |pixel: (i64, i64), depth: f64| {
-                    if self.is_in_bounds(pixel.0, pixel.1) && depth > 0.0 {
-                        self.plot_character(
-                            pixel.0 as u16,
-                            pixel.1 as u16,
-                            // Ceiling the number instead of rounding ensures the face is always behind
-                            // the edge, to account for imprecision in some calculations
-                            depth.ceil() as i64,
-                            face_style,
-                            shape_id,
-                            DrawType::Face,
-                            frame,
-                        );
-                    }
-                });
-            }
-        }
+    /// Write the frame in the framebuffer to another target, such as a recording
+    pub fn render_to(&self, target: &mut dyn RenderTarget) -> io::Result<()> {
+        target.present(&self.framebuffer)
+    }
+
+    /// Send every frame rendered from now on to `sink` as well
+    pub fn add_sink(&mut self, sink: Box<dyn RenderTarget>) {
+        self.sinks.push(sink);
     }
 
     pub fn render(&mut self) {
-        let mut prev_style: Style = (' ', Color::Reset, Decor::None);
-        let mut lock = io::stdout().lock();
-        write!(lock, "{esc}[H", esc = 27 as char).unwrap();
-        write!(lock, "{}", get_style_escape(prev_style)).unwrap();
-        for i in 0..self.display.len() {
-            let item = &self.display[i];
-
-            if item.style.1 != prev_style.1 || item.style.2 != prev_style.2 {
-                prev_style = item.style;
-                write!(lock, "{}", get_style_escape(prev_style)).unwrap();
-            }
+        self.output.present(&self.framebuffer).unwrap();
 
-            if i != 0 && i % (self.term_width as usize) == 0 {
-                write!(lock, "\n{}", item.style.0).unwrap();
-            } else {
-                write!(lock, "{}", item.style.0).unwrap();
-            }
+        for sink in &mut self.sinks {
+            sink.present(&self.framebuffer).unwrap();
         }
-        lock.flush().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::IsoCamera;
+
+    #[test]
+    fn downgrade_rgb() {
+        let orange = Color::Rgb(255, 135, 0);
+
+        assert_eq!(orange.downgrade(ColorSupport::TrueColor), orange);
+        assert_eq!(orange.downgrade(ColorSupport::Indexed), Color::Indexed(208));
+        assert_eq!(orange.downgrade(ColorSupport::Basic), Color::Indexed(3));
+        assert_eq!(
+            Color::Rgb(128, 128, 128).downgrade(ColorSupport::Indexed),
+            Color::Indexed(244)
+        );
+    }
+
+    #[test]
+    fn downgrade_indexed() {
+        assert_eq!(
+            Color::Indexed(21).downgrade(ColorSupport::Basic),
+            Color::Indexed(4)
+        );
+        assert_eq!(
+            Color::Indexed(3).downgrade(ColorSupport::Basic),
+            Color::Indexed(3)
+        );
+        assert_eq!(Color::Blue.downgrade(ColorSupport::Basic), Color::Blue);
+    }
+
+    #[test]
+    fn resolve_size_priority() {
+        let term_size = || {
+            Some(Size {
+                rows: 30,
+                cols: 100,
+            })
+        };
+        let env_size = || Some(Size { rows: 20, cols: 60 });
+
+        assert_eq!(
+            resolve_size(Some((40, 10)), term_size, env_size),
+            Size { rows: 10, cols: 40 }
+        );
+        assert_eq!(
+            resolve_size(None, term_size, env_size),
+            Size {
+                rows: 30,
+                cols: 100
+            }
+        );
+        assert_eq!(
+            resolve_size(None, || None, env_size),
+            Size { rows: 20, cols: 60 }
+        );
+        assert_eq!(
+            resolve_size(None, || None, || None),
+            Size {
+                rows: DEFAULT_SIZE.1,
+                cols: DEFAULT_SIZE.0
+            }
+        );
+    }
+
+    #[test]
+    fn pick_up_resizes() {
+        let size = resolve_size(None, get_term_size, get_env_size);
+        let mut terminal = Terminal {
+            term_width: 1,
+            term_height: 1,
+            fixed_size: None,
+            is_tty: false,
+            framebuffer: Framebuffer::new((1, 1), SubCellMode::Character),
+            output: WriterTarget::new(io::stdout(), ColorSupport::Basic),
+            cameras: vec![],
+            sinks: vec![],
+            events: VecDeque::new(),
+        };
+        let camera = Rc::new(RefCell::new(IsoCamera::default(terminal.get_term_size())));
+        terminal.register_camera(camera.clone());
+
+        // Change the mode without updating the camera, so only the resize can update it
+        terminal
+            .get_framebuffer_mut()
+            .set_subcell_mode(SubCellMode::Braille);
+
+        RESIZED.store(true, Ordering::SeqCst);
+        terminal.pre_render();
+
+        let screen_size = (size.cols * 2, size.rows * 4);
+        assert_eq!(
+            terminal.get_framebuffer().get_size(),
+            (size.cols, size.rows)
+        );
+        assert_eq!(terminal.get_term_size(), screen_size);
+        assert_eq!(camera.borrow().get_screen_size(), screen_size);
+        assert_eq!(
+            camera.borrow().get_pixel_aspect(),
+            SubCellMode::Braille.pixel_aspect()
+        );
+
+        // Resizes that haven't been polled are replaced by the latest one
+        terminal.term_width = 1;
+        RESIZED.store(true, Ordering::SeqCst);
+        terminal.pre_render();
+        assert!(matches!(
+            terminal.poll_event(),
+            Some(TerminalEvent::Resize(size)) if size == screen_size
+        ));
+        assert!(terminal.poll_event().is_none());
     }
 }
@@ -0,0 +1,83 @@
+use std::{fs, io, path::Path};
+
+use crate::terminal::{Color, Decor, Style};
+
+/// Texture coordinates, (u, v), where (0, 0) is the top left of a texture and (1, 1) the bottom
+/// right
+pub type Uv = (f64, f64);
+
+/// Grid of styled characters that can be mapped onto faces
+#[derive(Clone, PartialEq, Debug)]
+pub struct Texture {
+    width: usize,
+    height: usize,
+    /// Cells of the texture, row by row
+    cells: Vec<Style>,
+}
+
+impl Texture {
+    /// Texture `width` cells wide, from `cells` given row by row. Missing cells are filled with
+    /// spaces.
+    pub fn new(width: usize, mut cells: Vec<Style>) -> Texture {
+        let width = width.max(1);
+        let height = cells.len().div_ceil(width).max(1);
+
+        cells.resize(width * height, (' ', Color::Reset, Decor::None));
+
+        Texture {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Texture with the characters of `text`, one row per line, all drawn with `color` and
+    /// `decor`. Short lines are padded with spaces.
+    pub fn from_text(text: &str, color: Color, decor: Decor) -> Texture {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let cells = lines
+            .iter()
+            .flat_map(|line| {
+                (0..width).map(|x| (line.get(x).copied().unwrap_or(' '), color, decor))
+            })
+            .collect();
+
+        Texture::new(width, cells)
+    }
+
+    /// Load a texture from a text file, see `from_text`
+    pub fn load(path: impl AsRef<Path>, color: Color, decor: Decor) -> io::Result<Texture> {
+        Ok(Texture::from_text(&fs::read_to_string(path)?, color, decor))
+    }
+
+    /// Size in cells, (width, height)
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Cell at `uv`. Coordinates outside the texture are clamped to its border.
+    pub fn sample(&self, (u, v): Uv) -> Style {
+        let x = ((u * self.width as f64).floor().max(0.0) as usize).min(self.width - 1);
+        let y = ((v * self.height as f64).floor().max(0.0) as usize).min(self.height - 1);
+
+        self.cells[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_text() {
+        let texture = Texture::from_text("ab\ncde\n", Color::Red, Decor::None);
+
+        assert_eq!(texture.get_size(), (3, 2));
+        assert_eq!(texture.sample((0.0, 0.0)), ('a', Color::Red, Decor::None));
+        assert_eq!(texture.sample((0.9, 0.2)), (' ', Color::Red, Decor::None));
+        assert_eq!(texture.sample((0.5, 0.5)), ('d', Color::Red, Decor::None));
+        assert_eq!(texture.sample((1.0, 1.0)), ('e', Color::Red, Decor::None));
+        assert_eq!(texture.sample((-1.0, 2.0)), ('c', Color::Red, Decor::None));
+    }
+}
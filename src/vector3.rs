@@ -134,10 +134,6 @@ impl Vector3 {
             z: val,
         }
     }
-
-    pub fn to_string(&self) -> String {
-        format!("({}, {}, {})", self.x, self.y, self.z)
-    }
 }
 
 impl ops::Add<Vector3> for Vector3 {
@@ -307,12 +303,12 @@ impl From<[i64; 3]> for Vector3 {
 
 impl fmt::Display for Vector3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
 
 impl fmt::Debug for Vector3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
@@ -1,9 +1,10 @@
-use crate::world_object::WorldObject;
+use crate::{lighting::Light, world_object::WorldObject};
 use std::collections::BTreeMap;
 use std::ops::{Deref, DerefMut};
 
 pub struct World {
     objects: BTreeMap<u64, Box<dyn WorldObject>>,
+    lights: Vec<Box<dyn Light>>,
     counter: u64,
 }
 
@@ -11,6 +12,7 @@ impl World {
     pub fn new() -> World {
         World {
             objects: BTreeMap::new(),
+            lights: Vec::new(),
             counter: 0,
         }
     }
@@ -19,6 +21,36 @@ impl World {
         self.objects.insert(self.counter, obj);
         self.counter += 1;
     }
+
+    pub fn add_light(&mut self, light: Box<dyn Light>) {
+        self.lights.push(light);
+    }
+
+    pub fn get_lights(&self) -> &[Box<dyn Light>] {
+        &self.lights
+    }
+
+    pub fn get_lights_mut(&mut self) -> &mut [Box<dyn Light>] {
+        &mut self.lights
+    }
+
+    /// Recalculate where objects block light. Call after objects and lights have moved.
+    pub fn update_shadows(&mut self) {
+        let objects: Vec<&dyn WorldObject> =
+            self.objects.values().map(|obj| obj.as_ref()).collect();
+
+        for light in &mut self.lights {
+            if light.casts_shadows() {
+                light.update_shadows(&objects);
+            }
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> World {
+        World::new()
+    }
 }
 
 impl Deref for World {
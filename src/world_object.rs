@@ -1,4 +1,9 @@
+use crate::feature_edges::FeatureEdges;
+use crate::lighting::{Material, Ramp, ShadingMode, average_normals};
+use crate::line_glyph::LineGlyphs;
+use crate::render_mode::RenderMode;
 use crate::terminal;
+use crate::texture::{Texture, Uv};
 use crate::vector3::Vector3;
 
 pub trait WorldObject {
@@ -15,6 +20,17 @@ pub trait WorldObject {
         Vec::new()
     }
 
+    /// Which parts of the object are drawn, if it differs from the render mode of the framebuffer
+    fn render_mode(&self) -> Option<RenderMode> {
+        None
+    }
+
+    /// Edges derived from `triangles` each frame and drawn along with `edges`, if any. Lets
+    /// meshes made only of triangles be outlined.
+    fn feature_edges(&self) -> Option<FeatureEdges> {
+        None
+    }
+
     fn vertex_style(&self) -> terminal::Style {
         ('X', terminal::Color::Reset, terminal::Decor::None)
     }
@@ -23,9 +39,54 @@ pub trait WorldObject {
         ('O', terminal::Color::Reset, terminal::Decor::None)
     }
 
+    /// How the characters of edges are chosen. Only used in the `Character` sub-cell mode.
+    fn line_glyphs(&self) -> LineGlyphs {
+        LineGlyphs::Fixed
+    }
+
+    /// Ramp that edges are drawn with when anti-aliased, picked by how much of each cell the edge
+    /// covers. The character and color of the edge are kept where the ramp doesn't replace them.
+    /// Edges are drawn aliased if this is None.
+    fn edge_ramp(&self) -> Option<Ramp> {
+        None
+    }
+
     fn face_style(&self) -> terminal::Style {
         ('.', terminal::Color::Reset, terminal::Decor::None)
     }
 
+    /// How faces react to light, when the world has lights. The character and decor of
+    /// `face_style` are used where the material's ramp doesn't replace them.
+    fn material(&self) -> Material {
+        Material::matte(self.face_style().1)
+    }
+
+    /// Material of the face at `index` in `triangles`, if it differs from `material`
+    fn face_material(&self, _index: usize) -> Option<Material> {
+        None
+    }
+
+    /// Texture drawn on faces that have texture coordinates, instead of the face style
+    fn texture(&self) -> Option<&Texture> {
+        None
+    }
+
+    /// Texture coordinates of the corners of each triangle, in the same order as `triangles`.
+    /// Triangles without coordinates are drawn untextured.
+    fn triangle_uvs(&self) -> Vec<(Uv, Uv, Uv)> {
+        Vec::new()
+    }
+
+    fn shading_mode(&self) -> ShadingMode {
+        ShadingMode::Flat
+    }
+
+    /// Normals of each vertex, used for smooth shading. By default, the normals of the faces
+    /// around each vertex are averaged, so curved meshes only need to override this if they know
+    /// their exact normals.
+    fn vertex_normals(&self) -> Vec<Vector3> {
+        average_normals(&self.vectices(), &self.triangles())
+    }
+
     fn update(&mut self, _frame: u64) {}
 }